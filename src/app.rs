@@ -1,34 +1,55 @@
 use crate::{
+    clipboard,
     comments_nav::{
-        current_index_from_scroll, next_comment_index, next_sibling_or_outer_index,
-        previous_comment_index, previous_sibling_or_parent_index,
+        ancestor_indices, nearest_parent_index, next_author_index, next_comment_index,
+        next_sibling_or_outer_index, next_top_level_index, previous_comment_index,
+        previous_sibling_or_parent_index, top_level_ancestor_index,
     },
+    config::{Config, config_path},
     event::{AppEvent, Event, EventHandler, PostsFetchMode, PostsFetchResult},
-    hn::{Comment, HackerNewsApi, Item, StoryFeed},
+    hn::{
+        Comment, CommentOrder, CommentThread, CommentsPage, DEFAULT_RETRIES, FetchError,
+        HackerNewsApi, Item, StoryFeed, discussion_url, url_domain,
+    },
+    html::{self, blocks_to_plain_text, decode_html_entities, parse_hn_html},
     input::{
-        BookmarksKeyAction, CommentsKeyAction, FeedsKeyAction, GlobalKeyAction, PostsKeyAction,
-        map_bookmarks_action, map_comments_action, map_feeds_action, map_global_action,
-        map_posts_action,
+        AlertsKeyAction, BookmarksKeyAction, CommentsKeyAction, FeedsKeyAction, GlobalKeyAction,
+        LinkPickerKeyAction, PaletteKeyAction, PostsKeyAction, ProfileKeyAction, ReaderKeyAction,
+        map_alerts_action, map_bookmarks_action, map_comments_action, map_feeds_action,
+        map_global_action, map_link_picker_action, map_palette_action, map_posts_action,
+        map_profile_action, map_reader_action,
     },
+    links::{LinkPicker, LinkScope},
+    mute::MuteFilter,
+    opener,
+    palette::{CommandPalette, MAX_VISIBLE_MATCHES, PaletteCommand},
+    posts_view::{PostStats, PostsView},
+    profile::{ProfileView, about_text, submission_label},
+    reader::{Article, ArticleBlock, ReaderClient},
+    state::{SavedState, state_path},
     ui::{
-        POST_META_COLOR, POST_SELECTED_COLOR, Pane, SPINNER_FRAMES,
-        comment_lines as build_comment_lines, format_age, instructions_line, instructions_pane_for,
-        pane_border_style, pane_title_with_shortcut,
+        ALERT_BADGE_COLOR, COMMENT_LINK_COLOR, CommentsView, POST_META_COLOR, POST_SELECTED_COLOR,
+        Pane, SPINNER_FRAMES, article_lines, comment_lines as build_comment_lines,
+        failed_items_line, format_age, instructions_line, instructions_pane_for, pane_border_style,
+        pane_title_with_shortcut, popup_area,
     },
+    watch::{Alert, WatchList},
 };
 use chrono::Local;
 use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     DefaultTerminal, Frame,
     layout::{Constraint, Layout},
     style::{Style, Stylize},
     symbols::border,
-    text::Line,
-    widgets::{Block, List, ListItem, ListState, Paragraph, Tabs},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListItem, ListState, Paragraph, Tabs, Wrap},
 };
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
+    env,
+    path::{Path, PathBuf},
     time::{Duration, Instant},
 };
 use tokio_util::sync::CancellationToken;
@@ -41,7 +62,13 @@ pub struct App {
     story_ids: Vec<u64>,
     next_story_index: usize,
     has_more_posts: bool,
+    /// Every loaded post of the current feed in rank order; `posts` is the
+    /// subset shown after the domain filter.
+    feed_posts: Vec<Post>,
+    /// Stories of the loaded pages that failed to load; a refresh retries them.
+    failed_items: Vec<u64>,
     posts: Vec<Post>,
+    domain_filter: Option<String>,
     bookmarks: Vec<Post>,
     posts_notice: Option<String>,
     selected_feed: FeedTab,
@@ -58,14 +85,43 @@ pub struct App {
     comments: Vec<Comment>,
     comments_for_post_id: Option<u64>,
     comments_loading: bool,
+    comments_loading_more: bool,
+    comments_thread: Option<CommentThread>,
     comments_error: Option<String>,
     comments_notice: Option<String>,
     comments_scroll: u16,
     comments_viewport_height: usize,
     comment_line_count: usize,
     comment_start_lines: Vec<u16>,
+    /// Index of the comment that actions target. Free scrolling leaves it
+    /// where it is; loading more comments keeps it on the same comment.
+    comment_cursor: Option<usize>,
+    comment_order: CommentOrder,
     comments_cache: HashMap<u64, CachedComments>,
+    reader_client: ReaderClient,
+    reader: Option<ReaderView>,
+    /// Recently read articles by URL, least recently used first.
+    article_cache: Vec<(String, Article)>,
     bookmarks_collapsed: bool,
+    command_palette: Option<CommandPalette>,
+    link_picker: Option<LinkPicker>,
+    profile: Option<ProfileView>,
+    status_message: Option<String>,
+    config: Config,
+    /// Whether copies go through OSC 52 before the configured clipboard command.
+    osc52_supported: bool,
+    saved_state: SavedState,
+    state_path: Option<PathBuf>,
+    mute_filter: MuteFilter,
+    revealed_comment_ids: Vec<u64>,
+    watch_list: WatchList,
+    alerts: Vec<Alert>,
+    /// Every item that raised an alert, so dismissed alerts stay dismissed.
+    alerted_item_ids: HashSet<u64>,
+    alerts_state: ListState,
+    watch_scan_started_at: Option<Instant>,
+    watch_scan_running: bool,
+    viewed_post: Option<Post>,
 }
 
 #[derive(Debug, Clone)]
@@ -73,6 +129,10 @@ struct Post {
     id: u64,
     title: String,
     url: String,
+    /// Host without `www.`, or `self` for posts without a link.
+    domain: String,
+    /// Text of Ask HN and other self posts.
+    body: Vec<html::Block>,
     post_type: PostType,
     points: u64,
     comments: u64,
@@ -80,10 +140,22 @@ struct Post {
     published_at: u64,
 }
 
+impl Post {
+    fn stats(&self) -> PostStats {
+        PostStats {
+            points: self.points,
+            comments: self.comments,
+            published_at: self.published_at,
+            is_job: self.post_type == PostType::Job,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum PostType {
     Story,
     Job,
+    Poll,
 }
 
 impl PostType {
@@ -91,19 +163,21 @@ impl PostType {
         match kind {
             Some("story") => Some(Self::Story),
             Some("job") => Some(Self::Job),
+            Some("poll") => Some(Self::Poll),
             _ => None,
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum FeedTab {
+pub enum FeedTab {
     Top,
     New,
     Ask,
     Show,
     Jobs,
     Best,
+    Following,
 }
 
 #[derive(Debug, Clone)]
@@ -112,27 +186,64 @@ struct CachedFeed {
     next_story_index: usize,
     has_more_posts: bool,
     posts: Vec<Post>,
+    failed_items: Vec<u64>,
     selected_index: Option<usize>,
     last_fetched: Option<String>,
 }
 
+/// Article shown in the reader pane. Without an article or error it is loading.
+#[derive(Debug, Clone)]
+struct ReaderView {
+    url: String,
+    title: String,
+    article: Option<Article>,
+    error: Option<String>,
+    scroll: u16,
+    line_count: usize,
+    viewport_height: usize,
+}
+
+impl ReaderView {
+    fn loading(&self) -> bool {
+        self.article.is_none() && self.error.is_none()
+    }
+
+    fn max_scroll(&self) -> u16 {
+        self.line_count.saturating_sub(self.viewport_height) as u16
+    }
+
+    fn page_step(&self) -> u16 {
+        self.viewport_height.saturating_sub(1).max(1) as u16
+    }
+
+    fn scroll_up(&mut self, amount: u16) {
+        self.scroll = self.scroll.saturating_sub(amount);
+    }
+
+    fn scroll_down(&mut self, amount: u16) {
+        self.scroll = self.scroll.saturating_add(amount).min(self.max_scroll());
+    }
+}
+
 #[derive(Debug, Clone)]
 struct CachedComments {
     comments: Vec<Comment>,
+    thread: Option<CommentThread>,
     fetched_at: Instant,
 }
 
 impl FeedTab {
-    const ALL: [Self; 6] = [
+    pub const ALL: [Self; 7] = [
         Self::Top,
         Self::New,
         Self::Ask,
         Self::Show,
         Self::Jobs,
         Self::Best,
+        Self::Following,
     ];
 
-    fn label(self) -> &'static str {
+    pub fn label(self) -> &'static str {
         match self {
             Self::Top => "top",
             Self::New => "new",
@@ -140,6 +251,7 @@ impl FeedTab {
             Self::Show => "show",
             Self::Jobs => "jobs",
             Self::Best => "best",
+            Self::Following => "following",
         }
     }
 
@@ -151,17 +263,20 @@ impl FeedTab {
             Self::Show => "Show HN",
             Self::Jobs => "Jobs",
             Self::Best => "Best Stories",
+            Self::Following => "Following",
         }
     }
 
-    fn api_feed(self) -> StoryFeed {
+    /// The HN story list behind the tab. `Following` is built from followed users.
+    fn api_feed(self) -> Option<StoryFeed> {
         match self {
-            Self::Top => StoryFeed::Top,
-            Self::New => StoryFeed::New,
-            Self::Ask => StoryFeed::Ask,
-            Self::Show => StoryFeed::Show,
-            Self::Jobs => StoryFeed::Jobs,
-            Self::Best => StoryFeed::Best,
+            Self::Top => Some(StoryFeed::Top),
+            Self::New => Some(StoryFeed::New),
+            Self::Ask => Some(StoryFeed::Ask),
+            Self::Show => Some(StoryFeed::Show),
+            Self::Jobs => Some(StoryFeed::Jobs),
+            Self::Best => Some(StoryFeed::Best),
+            Self::Following => None,
         }
     }
 
@@ -178,18 +293,43 @@ const POSTS_PAGE_SIZE: usize = 30;
 const LOAD_MORE_TRIGGER_NUMERATOR: usize = 3;
 const LOAD_MORE_TRIGGER_DENOMINATOR: usize = 4;
 const COMMENTS_CACHE_REFRESH_AFTER_SECS: u64 = 90;
+const PROFILE_SUBMISSION_LIMIT: usize = 30;
+const FOLLOWING_SUBMISSIONS_PER_USER: usize = 20;
+const SELF_POST_DOMAIN: &str = "self";
+const NO_FOLLOWED_USERS_NOTICE: &str =
+    "You are not following anyone yet. Press f in a user's profile to follow them.";
+const COMMENTS_PAGE_SIZE: usize = 75;
+const WATCH_SCAN_INTERVAL_SECS: u64 = 300;
+const WATCH_SCAN_ITEMS_PER_FEED: usize = 30;
+const MAX_ALERTS: usize = 100;
+const MAX_ALERTS_PANE_ROWS: u16 = 6;
+const MAX_CACHED_ARTICLES: usize = 20;
 
 impl App {
     pub fn new() -> Self {
+        Self::with_paths(config_path(), state_path())
+    }
+
+    /// Builds the app around the given config and state files. Without a
+    /// state path nothing is saved; without a config path defaults are used.
+    pub fn with_paths(config_path: Option<PathBuf>, state_path: Option<PathBuf>) -> Self {
+        let (config, config_error) = load_config(config_path.as_deref());
+        let (saved_state, state_error) = load_saved_state(state_path.as_deref());
+        let (mute_filter, mute_error) = MuteFilter::new(&saved_state.mutes);
+        let (watch_list, watch_error) = WatchList::new(&config.watch);
+
         Self {
             running: true,
-            hn_client: HackerNewsApi::new(),
+            hn_client: hn_client(&config),
             events: EventHandler::new(),
             loading_frame: 0,
             story_ids: Vec::new(),
             next_story_index: 0,
             has_more_posts: true,
+            feed_posts: Vec::new(),
+            failed_items: Vec::new(),
             posts: Vec::new(),
+            domain_filter: None,
             bookmarks: Vec::new(),
             posts_notice: None,
             selected_feed: FeedTab::Top,
@@ -206,14 +346,38 @@ impl App {
             comments: Vec::new(),
             comments_for_post_id: None,
             comments_loading: false,
+            comments_loading_more: false,
+            comments_thread: None,
             comments_error: None,
             comments_notice: None,
             comments_scroll: 0,
             comments_viewport_height: 0,
             comment_line_count: 0,
             comment_start_lines: Vec::new(),
+            comment_cursor: None,
+            comment_order: CommentOrder::default(),
             comments_cache: HashMap::new(),
+            reader_client: ReaderClient::new(),
+            reader: None,
+            article_cache: Vec::new(),
             bookmarks_collapsed: false,
+            command_palette: None,
+            link_picker: None,
+            profile: None,
+            status_message: config_error.or(state_error).or(mute_error).or(watch_error),
+            config,
+            osc52_supported: clipboard::osc52_supported(),
+            saved_state,
+            state_path,
+            mute_filter,
+            revealed_comment_ids: Vec::new(),
+            watch_list,
+            alerts: Vec::new(),
+            alerted_item_ids: HashSet::new(),
+            alerts_state: ListState::default(),
+            watch_scan_started_at: None,
+            watch_scan_running: false,
+            viewed_post: None,
         }
     }
 
@@ -256,17 +420,36 @@ impl App {
             spinner,
         );
 
-        let outer_block = Block::bordered()
+        let mut outer_block = Block::bordered()
             .title(title.centered())
             .title_bottom(instructions.centered())
             .border_set(border::THICK);
+        if let Some(message) = self.status_message.as_deref() {
+            outer_block = outer_block.title(
+                Line::from(format!(" {message} "))
+                    .right_aligned()
+                    .style(Style::new().fg(POST_SELECTED_COLOR)),
+            );
+        }
 
         let content_area = outer_block.inner(frame.area());
         frame.render_widget(outer_block, frame.area());
 
-        let layout = Layout::vertical([Constraint::Length(3), Constraint::Min(0)]);
+        let alerts_height = if self.alerts.is_empty() {
+            0
+        } else {
+            (self.alerts.len() as u16).min(MAX_ALERTS_PANE_ROWS) + 2
+        };
+        let layout = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(alerts_height),
+        ]);
         let areas = layout.split(content_area);
         self.render_feed_tabs(frame, areas[0]);
+        if !self.alerts.is_empty() {
+            self.render_alerts_list(frame, areas[2]);
+        }
 
         if self.bookmarks_visible() {
             let bookmarks_width = self.bookmarks_width_percent();
@@ -277,23 +460,38 @@ impl App {
             let panes = layout.split(areas[1]);
             self.render_bookmarks_list(frame, panes[0]);
 
-            if self.comments_open {
-                let post_comment_layout =
-                    Layout::horizontal([Constraint::Percentage(33), Constraint::Percentage(67)]);
-                let post_comment_panes = post_comment_layout.split(panes[1]);
-                self.render_posts_list(frame, post_comment_panes[0]);
-                self.render_comments_pane(frame, post_comment_panes[1], spinner);
-            } else {
-                self.render_posts_list(frame, panes[1]);
-            }
-        } else if self.comments_open {
-            let layout =
-                Layout::horizontal([Constraint::Percentage(33), Constraint::Percentage(67)]);
-            let panes = layout.split(areas[1]);
-            self.render_posts_list(frame, panes[0]);
-            self.render_comments_pane(frame, panes[1], spinner);
+            self.render_main_panes(frame, panes[1], spinner);
         } else {
-            self.render_posts_list(frame, areas[1]);
+            self.render_main_panes(frame, areas[1], spinner);
+        }
+
+        self.render_link_picker(frame);
+        self.render_profile(frame, spinner);
+        self.render_command_palette(frame);
+    }
+
+    /// Renders the posts list, with the comments and reader panes side by side to
+    /// its right when they are open.
+    fn render_main_panes(&mut self, frame: &mut Frame, area: ratatui::layout::Rect, spinner: &str) {
+        if !self.comments_open && self.reader.is_none() {
+            self.render_posts_list(frame, area);
+            return;
+        }
+
+        let layout = Layout::horizontal([Constraint::Percentage(33), Constraint::Percentage(67)]);
+        let panes = layout.split(area);
+        self.render_posts_list(frame, panes[0]);
+
+        match (self.comments_open, self.reader.is_some()) {
+            (true, true) => {
+                let detail_layout =
+                    Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]);
+                let detail_panes = detail_layout.split(panes[1]);
+                self.render_comments_pane(frame, detail_panes[0], spinner);
+                self.render_reader_pane(frame, detail_panes[1], spinner);
+            }
+            (true, false) => self.render_comments_pane(frame, panes[1], spinner),
+            _ => self.render_reader_pane(frame, panes[1], spinner),
         }
     }
 
@@ -345,13 +543,26 @@ impl App {
                     } else {
                         Style::new()
                     };
-                    let title_line = Line::from(format!(
-                        "{:>width$}. {}",
+                    let mut title_spans = vec![Span::raw(format!(
+                        "{:>width$}. ",
                         i + 1,
-                        post.title,
                         width = post_rank_width
-                    ))
-                    .style(title_style);
+                    ))];
+                    if let Some(alert) = self.alerts.iter().find(|alert| alert.item_id == post.id) {
+                        title_spans.push(Span::styled(
+                            format!("[{}]", alert.rule),
+                            Style::new().fg(ALERT_BADGE_COLOR).bold(),
+                        ));
+                        title_spans.push(Span::raw(" "));
+                    }
+                    title_spans.push(Span::raw(post.title.clone()));
+                    if !post.domain.is_empty() {
+                        title_spans.push(Span::styled(
+                            format!(" ({})", post.domain),
+                            Style::new().fg(POST_META_COLOR),
+                        ));
+                    }
+                    let title_line = Line::from(title_spans).style(title_style);
                     let meta_text = match post.post_type {
                         PostType::Job => format!(
                             "{}job • {} points • by {} • {}",
@@ -360,6 +571,14 @@ impl App {
                             post.author,
                             format_age(post.published_at)
                         ),
+                        PostType::Poll => format!(
+                            "{}poll • {} points • {} comments • by {} • {}",
+                            meta_indent,
+                            post.points,
+                            post.comments,
+                            post.author,
+                            format_age(post.published_at)
+                        ),
                         PostType::Story => format!(
                             "{}{} points • {} comments • by {} • {}",
                             meta_indent,
@@ -375,13 +594,24 @@ impl App {
                 .collect()
         };
 
+        let mut posts_title = match self.domain_filter.as_deref() {
+            Some(domain) => format!("{} from {domain}", self.selected_feed.posts_title()),
+            None => self.selected_feed.posts_title().to_string(),
+        };
+        let view = self.posts_view();
+        if !view.is_default() {
+            posts_title.push_str(&format!(" | {}", view.summary()));
+        }
         let mut block = Block::bordered().title(pane_title_with_shortcut(
-            self.selected_feed.posts_title(),
+            posts_title,
             '2',
             self.focus_pane,
             Pane::Posts,
         ));
         block = block.border_style(pane_border_style(self.focus_pane, Pane::Posts));
+        if let Some(line) = failed_items_line(self.failed_items.len()) {
+            block = block.title_bottom(line);
+        }
         if let Some(last_fetched) = self.last_fetched.as_deref() {
             block = block.title(
                 Line::from(format!("last fetched {last_fetched}"))
@@ -394,6 +624,40 @@ impl App {
         frame.render_stateful_widget(list, area, &mut self.list_state);
     }
 
+    fn render_alerts_list(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let items: Vec<ListItem> = self
+            .alerts
+            .iter()
+            .map(|alert| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("[{}] ", alert.rule),
+                        Style::new().fg(ALERT_BADGE_COLOR).bold(),
+                    ),
+                    Span::raw(alert.title.clone()),
+                    Span::styled(
+                        format!(" • {} points", alert.points),
+                        Style::new().fg(POST_META_COLOR),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let block = Block::bordered()
+            .title(pane_title_with_shortcut(
+                format!("Alerts ({})", self.alerts.len()),
+                '6',
+                self.focus_pane,
+                Pane::Alerts,
+            ))
+            .border_style(pane_border_style(self.focus_pane, Pane::Alerts));
+        let list = List::new(items)
+            .block(block)
+            .highlight_symbol("> ")
+            .highlight_style(Style::new().fg(POST_SELECTED_COLOR).bold());
+        frame.render_stateful_widget(list, area, &mut self.alerts_state);
+    }
+
     fn render_bookmarks_list(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
         let items: Vec<ListItem> = if self.bookmarks.is_empty() {
             vec![ListItem::new(
@@ -438,1379 +702,3885 @@ impl App {
         area: ratatui::layout::Rect,
         spinner: &str,
     ) {
-        let comments_title = self
+        let mut comments_title = self
             .comments_post()
-            .map(|post| format!("{} | {} comments", post.title, post.comments))
+            .map(|post| match self.comments_thread.as_ref() {
+                Some(thread) if thread.has_more() => format!(
+                    "{} | {} of {} comments loaded",
+                    post.title,
+                    self.comments.len(),
+                    thread.total()
+                ),
+                _ => format!("{} | {} comments", post.title, post.comments),
+            })
             .unwrap_or_else(|| "Comments".to_string());
+        if self.comment_order != CommentOrder::Ranked {
+            comments_title.push_str(&format!(" | {}", self.comment_order.label()));
+        }
+        if let Some(breadcrumbs) = self.focused_comment_breadcrumbs() {
+            comments_title.push_str(&format!(" | {breadcrumbs}"));
+        }
 
         let content_width = area.width.saturating_sub(2) as usize;
-        let (lines, comment_start_lines) = build_comment_lines(
+        let (mut lines, comment_start_lines) = build_comment_lines(
             spinner,
             content_width,
-            self.comments_for_post_id,
-            self.comments_loading,
-            self.comments_notice.as_deref(),
-            self.comments_error.as_deref(),
-            &self.comments,
+            &CommentsView {
+                post_id: self.comments_for_post_id,
+                loading: self.comments_loading,
+                notice: self.comments_notice.as_deref(),
+                error: self.comments_error.as_deref(),
+                comments: &self.comments,
+                followed_users: &self.saved_state.followed_users,
+                revealed: &self.revealed_comment_ids,
+                focused: self.focused_comment_index(),
+                post_author: self.comments_post().map(|post| post.author.as_str()),
+                post_body: self
+                    .comments_post()
+                    .map_or(&[], |post| post.body.as_slice()),
+                poll_options: self
+                    .comments_thread
+                    .as_ref()
+                    .map_or(&[], CommentThread::poll_options),
+            },
         );
+        if !self.comments.is_empty() {
+            if self.comments_loading_more {
+                lines.push(Line::from(format!("Loading more comments {spinner}")));
+            } else if self
+                .comments_thread
+                .as_ref()
+                .is_some_and(CommentThread::has_more)
+            {
+                lines.push(
+                    Line::from("Scroll down to load more comments")
+                        .style(Style::new().fg(POST_META_COLOR)),
+                );
+            }
+        }
         self.comment_start_lines = comment_start_lines;
         self.comment_line_count = lines.len();
         self.comments_viewport_height = area.height.saturating_sub(2) as usize;
         self.clamp_comments_scroll();
 
+        let mut block = Block::bordered()
+            .title(pane_title_with_shortcut(
+                comments_title,
+                '3',
+                self.focus_pane,
+                Pane::Comments,
+            ))
+            .border_style(pane_border_style(self.focus_pane, Pane::Comments));
+        if let Some(line) = failed_items_line(self.failed_comment_count()) {
+            block = block.title_bottom(line);
+        }
+        let widget = Paragraph::new(lines)
+            .block(block)
+            .scroll((self.comments_scroll, 0));
+
+        frame.render_widget(widget, area);
+    }
+
+    fn render_reader_pane(
+        &mut self,
+        frame: &mut Frame,
+        area: ratatui::layout::Rect,
+        spinner: &str,
+    ) {
+        let focus_pane = self.focus_pane;
+        let Some(reader) = self.reader.as_mut() else {
+            return;
+        };
+
+        let content_width = area.width.saturating_sub(2) as usize;
+        let lines = match (&reader.article, &reader.error) {
+            (Some(article), _) => article_lines(article, content_width),
+            (None, Some(error)) => vec![
+                Line::from(format!("Could not load article: {error}"))
+                    .style(Style::new().fg(POST_META_COLOR)),
+            ],
+            (None, None) => vec![Line::from(format!("Loading article {spinner}"))],
+        };
+        reader.line_count = lines.len();
+        reader.viewport_height = area.height.saturating_sub(2) as usize;
+        reader.scroll = reader.scroll.min(reader.max_scroll());
+
         let widget = Paragraph::new(lines)
             .block(
                 Block::bordered()
                     .title(pane_title_with_shortcut(
-                        comments_title,
-                        '3',
-                        self.focus_pane,
-                        Pane::Comments,
+                        format!("Reader | {}", reader.title),
+                        '5',
+                        focus_pane,
+                        Pane::Reader,
                     ))
-                    .border_style(pane_border_style(self.focus_pane, Pane::Comments)),
+                    .border_style(pane_border_style(focus_pane, Pane::Reader)),
             )
-            .scroll((self.comments_scroll, 0));
+            .scroll((reader.scroll, 0));
 
         frame.render_widget(widget, area);
     }
 
+    fn render_command_palette(&self, frame: &mut Frame) {
+        let Some(palette) = self.command_palette.as_ref() else {
+            return;
+        };
+
+        let matches = palette.matches();
+        let visible_rows = matches.len().clamp(1, MAX_VISIBLE_MATCHES);
+        let area = popup_area(frame.area(), 60, visible_rows as u16 + 4);
+        let block = Block::bordered()
+            .title("Commands")
+            .border_style(Style::new().fg(POST_SELECTED_COLOR));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let layout = Layout::vertical([Constraint::Length(2), Constraint::Min(0)]);
+        let rows = layout.split(inner);
+        frame.render_widget(
+            Paragraph::new(Line::from(format!(":{}", palette.query()))),
+            rows[0],
+        );
+
+        let items: Vec<ListItem> = if matches.is_empty() {
+            vec![ListItem::new(
+                Line::from("No matching commands").style(Style::new().fg(POST_META_COLOR)),
+            )]
+        } else {
+            matches
+                .into_iter()
+                .map(|entry| ListItem::new(Line::from(entry.label)))
+                .collect()
+        };
+        let mut state = ListState::default().with_selected(Some(palette.selected()));
+        let list = List::new(items)
+            .highlight_symbol("> ")
+            .highlight_style(Style::new().fg(POST_SELECTED_COLOR).bold());
+        frame.render_stateful_widget(list, rows[1], &mut state);
+    }
+
+    fn render_link_picker(&self, frame: &mut Frame) {
+        let Some(picker) = self.link_picker.as_ref() else {
+            return;
+        };
+
+        let visible = picker.visible();
+        let area = popup_area(frame.area(), 70, visible.len().clamp(1, 15) as u16 + 4);
+        let block = Block::bordered()
+            .title(picker.title())
+            .border_style(Style::new().fg(POST_SELECTED_COLOR));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let layout = Layout::vertical([Constraint::Length(2), Constraint::Min(0)]);
+        let rows = layout.split(inner);
+        let scope_hint = match picker.scope() {
+            LinkScope::Comment => "Tab: whole thread",
+            LinkScope::Thread => "Tab: this comment",
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(format!(
+                "Type a number to open: {} | {scope_hint}",
+                picker.typed()
+            )))
+            .style(Style::new().fg(POST_META_COLOR)),
+            rows[0],
+        );
+
+        let show_authors = picker.scope() == LinkScope::Thread;
+        let items: Vec<ListItem> = if visible.is_empty() {
+            vec![ListItem::new(
+                Line::from("No links").style(Style::new().fg(POST_META_COLOR)),
+            )]
+        } else {
+            visible
+                .into_iter()
+                .map(|entry| {
+                    let remaining = &entry.hint[picker.typed().len()..];
+                    let mut spans = vec![
+                        Span::styled(picker.typed().to_string(), Style::new().fg(POST_META_COLOR)),
+                        Span::styled(
+                            remaining.to_string(),
+                            Style::new().fg(POST_SELECTED_COLOR).bold(),
+                        ),
+                        Span::raw(" "),
+                        Span::styled(entry.url.clone(), Style::new().fg(COMMENT_LINK_COLOR)),
+                    ];
+                    if show_authors {
+                        spans.push(Span::styled(
+                            format!(" ({})", entry.author),
+                            Style::new().fg(POST_META_COLOR),
+                        ));
+                    }
+                    ListItem::new(Line::from(spans))
+                })
+                .collect()
+        };
+        let mut state = ListState::default().with_selected(Some(picker.selected()));
+        let list = List::new(items)
+            .highlight_symbol("> ")
+            .highlight_style(Style::new().bold());
+        frame.render_stateful_widget(list, rows[1], &mut state);
+    }
+
+    fn render_profile(&self, frame: &mut Frame, spinner: &str) {
+        let Some(view) = self.profile.as_ref() else {
+            return;
+        };
+
+        let area = popup_area(frame.area(), 70, frame.area().height.saturating_sub(4));
+        let (follow_marker, follow_hint) = if self.saved_state.is_followed(view.username()) {
+            (" [following]", "Unfollow <F>")
+        } else {
+            ("", "Follow <F>")
+        };
+        let block = Block::bordered()
+            .title(format!("User: {}{follow_marker}", view.username()))
+            .title_bottom(Line::from(follow_hint).right_aligned())
+            .border_style(Style::new().fg(POST_SELECTED_COLOR));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let Some(profile) = view.profile() else {
+            let message = match view.error() {
+                Some(error) => format!("Could not load profile: {error}"),
+                None => format!("Loading profile {spinner}"),
+            };
+            frame.render_widget(
+                Paragraph::new(message).style(Style::new().fg(POST_META_COLOR)),
+                inner,
+            );
+            return;
+        };
+
+        let layout = Layout::vertical([Constraint::Percentage(35), Constraint::Min(0)]);
+        let rows = layout.split(inner);
+
+        let mut header = vec![
+            Line::from(vec![
+                Span::styled(profile.user.id.clone(), Style::new().bold()),
+                Span::styled(
+                    format!(" | {} karma", profile.user.karma),
+                    Style::new().fg(POST_SELECTED_COLOR).bold(),
+                ),
+                Span::styled(
+                    format!(" | joined {}", format_age(profile.user.created)),
+                    Style::new().fg(POST_META_COLOR),
+                ),
+            ]),
+            Line::default(),
+        ];
+        header.extend(
+            about_text(profile)
+                .lines()
+                .map(|line| Line::from(line.to_string())),
+        );
+        frame.render_widget(Paragraph::new(header).wrap(Wrap { trim: false }), rows[0]);
+
+        let items: Vec<ListItem> = if profile.submissions.is_empty() {
+            vec![ListItem::new(
+                Line::from("No recent submissions").style(Style::new().fg(POST_META_COLOR)),
+            )]
+        } else {
+            profile
+                .submissions
+                .iter()
+                .map(|item| {
+                    ListItem::new(Line::from(vec![
+                        Span::raw(submission_label(item)),
+                        Span::styled(
+                            format!(" {}", format_age(item.time.unwrap_or_default())),
+                            Style::new().fg(POST_META_COLOR),
+                        ),
+                    ]))
+                })
+                .collect()
+        };
+        let mut state = ListState::default().with_selected(Some(view.selected()));
+        let list = List::new(items)
+            .block(Block::default().title("Recent submissions <Enter/O>"))
+            .highlight_symbol("> ")
+            .highlight_style(Style::new().fg(POST_SELECTED_COLOR).bold());
+        frame.render_stateful_widget(list, rows[1], &mut state);
+    }
+
     fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<()> {
-        if let Some(action) = map_global_action(key_event) {
-            match action {
-                GlobalKeyAction::Exit => self.exit(),
-                GlobalKeyAction::FocusNextPane => self.focus_next_pane(),
-                GlobalKeyAction::FocusPreviousPane => self.focus_previous_pane(),
-                GlobalKeyAction::PaneShortcut(shortcut) => self.handle_pane_shortcut(shortcut),
-                GlobalKeyAction::Refresh => self.events.send(AppEvent::Refresh),
-                GlobalKeyAction::Quit => self.events.send(AppEvent::Quit),
+        self.status_message = None;
+
+        if self.link_picker.is_some() {
+            if map_global_action(key_event) == Some(GlobalKeyAction::Exit) {
+                self.exit();
+            } else {
+                self.handle_link_picker_key(key_event.code);
+            }
+            return Ok(());
+        }
+
+        if self.profile.is_some() {
+            if map_global_action(key_event) == Some(GlobalKeyAction::Exit) {
+                self.exit();
+            } else {
+                self.handle_profile_key(key_event.code);
+            }
+            return Ok(());
+        }
+
+        if self.command_palette.is_some() {
+            if map_global_action(key_event) == Some(GlobalKeyAction::Exit) {
+                self.exit();
+            } else {
+                self.handle_palette_key(key_event);
             }
             return Ok(());
         }
 
+        if let Some(action) = map_global_action(key_event) {
+            self.run_global_action(action);
+            return Ok(());
+        }
+
         match self.focus_pane {
             Pane::Feeds => self.handle_feeds_key(key_event.code),
             Pane::Posts => self.handle_posts_key(key_event.code),
             Pane::Comments => self.handle_comments_key(key_event.code),
             Pane::Bookmarks => self.handle_bookmarks_key(key_event.code),
+            Pane::Reader => self.handle_reader_key(key_event.code),
+            Pane::Alerts => self.handle_alerts_key(key_event.code),
         }
 
         Ok(())
     }
 
-    fn handle_feeds_key(&mut self, key_code: KeyCode) {
-        if let Some(action) = map_feeds_action(key_code) {
-            match action {
-                FeedsKeyAction::SelectPrevious => self.select_previous_feed(),
-                FeedsKeyAction::SelectNext => self.select_next_feed(),
-                FeedsKeyAction::FocusPosts => self.set_focus_pane(Pane::Posts),
+    fn run_global_action(&mut self, action: GlobalKeyAction) {
+        match action {
+            GlobalKeyAction::Exit => self.exit(),
+            GlobalKeyAction::FocusNextPane => self.focus_next_pane(),
+            GlobalKeyAction::FocusPreviousPane => self.focus_previous_pane(),
+            GlobalKeyAction::PaneShortcut(shortcut) => self.handle_pane_shortcut(shortcut),
+            GlobalKeyAction::Refresh => self.events.send(AppEvent::Refresh),
+            GlobalKeyAction::RetryFailed => {
+                if self.comments_open && self.failed_comment_count() > 0 {
+                    self.retry_failed_comments();
+                } else if !self.failed_items.is_empty() {
+                    self.retry_failed_posts();
+                } else {
+                    self.status_message = Some("No failed items to retry".to_string());
+                }
+            }
+            GlobalKeyAction::Quit => self.events.send(AppEvent::Quit),
+            GlobalKeyAction::OpenCommandPalette => {
+                self.command_palette = Some(CommandPalette::new());
+            }
+            GlobalKeyAction::OpenItemPrompt => {
+                self.command_palette = Some(CommandPalette::with_query("open "));
             }
         }
     }
 
-    fn handle_pane_shortcut(&mut self, key: char) {
-        match key {
-            '1' => {
-                if !self.bookmarks_visible() {
-                    return;
-                }
+    fn handle_palette_key(&mut self, key_event: KeyEvent) {
+        let Some(action) = map_palette_action(key_event) else {
+            return;
+        };
+        let Some(palette) = self.command_palette.as_mut() else {
+            return;
+        };
 
-                if self.focus_pane == Pane::Bookmarks {
-                    if self.bookmarks_collapsed {
-                        self.open_bookmarks_pane();
-                    } else {
-                        self.bookmarks_collapsed = true;
+        match action {
+            PaletteKeyAction::Close => self.command_palette = None,
+            PaletteKeyAction::Submit => {
+                let command = palette.selected_command();
+                let query = palette.query().trim().to_string();
+                self.command_palette = None;
+                match command {
+                    Some(command) => self.run_palette_command(command),
+                    None if !query.is_empty() => {
+                        self.status_message = Some(format!("Unknown command: {query}"));
                     }
-                } else {
-                    self.open_bookmarks_pane();
+                    None => {}
                 }
             }
-            '2' => {
-                self.set_focus_pane(Pane::Posts);
+            PaletteKeyAction::SelectPrevious => palette.select_previous(),
+            PaletteKeyAction::SelectNext => palette.select_next(),
+            PaletteKeyAction::DeleteChar => palette.pop_char(),
+            PaletteKeyAction::Insert(ch) => palette.push_char(ch),
+        }
+    }
+
+    fn handle_link_picker_key(&mut self, key_code: KeyCode) {
+        let Some(action) = map_link_picker_action(key_code) else {
+            return;
+        };
+        let Some(picker) = self.link_picker.as_mut() else {
+            return;
+        };
+
+        let url = match action {
+            LinkPickerKeyAction::Close => {
+                self.link_picker = None;
+                return;
             }
-            '3' => {
-                if self.comments_open {
-                    self.set_focus_pane(Pane::Comments);
-                }
+            LinkPickerKeyAction::Submit => picker.selected_url(),
+            LinkPickerKeyAction::SelectPrevious => {
+                picker.select_previous();
+                None
             }
-            '4' => {
-                self.set_focus_pane(Pane::Feeds);
+            LinkPickerKeyAction::SelectNext => {
+                picker.select_next();
+                None
             }
-            _ => {}
+            LinkPickerKeyAction::ToggleScope => {
+                picker.toggle_scope();
+                None
+            }
+            LinkPickerKeyAction::DeleteHint => {
+                picker.pop_hint();
+                None
+            }
+            LinkPickerKeyAction::Hint(ch) => picker.push_hint(ch),
+        };
+
+        if let Some(url) = url {
+            self.link_picker = None;
+            self.events.send(AppEvent::OpenPost(url));
         }
-        self.ensure_focus_valid();
     }
 
-    fn handle_posts_key(&mut self, key_code: KeyCode) {
-        if let Some(action) = map_posts_action(key_code, self.comments_open) {
-            match action {
-                PostsKeyAction::SelectPrevious => self.select_previous(),
-                PostsKeyAction::SelectNextAndLoadMore => {
-                    self.select_next();
-                    self.load_more_posts();
+    fn handle_profile_key(&mut self, key_code: KeyCode) {
+        let Some(action) = map_profile_action(key_code) else {
+            return;
+        };
+        let Some(view) = self.profile.as_mut() else {
+            return;
+        };
+
+        match action {
+            ProfileKeyAction::Close => self.profile = None,
+            ProfileKeyAction::SelectPrevious => view.select_previous(),
+            ProfileKeyAction::SelectNext => view.select_next(),
+            ProfileKeyAction::OpenSubmission => {
+                let Some(item) = view.selected_submission() else {
+                    return;
+                };
+                let (id, is_story) = (item.id, item.title.is_some());
+                self.profile = None;
+                if is_story {
+                    self.open_item_by_id(id);
+                } else {
+                    self.events.send(AppEvent::OpenPost(discussion_url(id)));
                 }
-                PostsKeyAction::BookmarkSelected => self.bookmark_selected_post(),
-                PostsKeyAction::OpenComments => self.open_comments_for_selected(),
-                PostsKeyAction::OpenPost => self.open_selected_post(),
-                PostsKeyAction::CloseComments => self.close_comments_view(),
+            }
+            ProfileKeyAction::OpenInBrowser => {
+                let Some(item) = view.selected_submission() else {
+                    return;
+                };
+                let url = item.url.clone().unwrap_or_else(|| discussion_url(item.id));
+                self.events.send(AppEvent::OpenPost(url));
+            }
+            ProfileKeyAction::ToggleFollow => {
+                let username = view.username().to_string();
+                self.toggle_follow(&username);
             }
         }
     }
 
-    fn handle_comments_key(&mut self, key_code: KeyCode) {
-        if let Some(action) = map_comments_action(key_code) {
-            match action {
-                CommentsKeyAction::Close => self.close_comments_view(),
-                CommentsKeyAction::BookmarkPost => self.bookmark_comments_post(),
-                CommentsKeyAction::OpenPost => self.open_comments_post(),
-                CommentsKeyAction::JumpPrevious => self.jump_to_previous_comment(),
-                CommentsKeyAction::JumpNext => self.jump_to_next_comment(),
-                CommentsKeyAction::JumpPreviousSibling => self.jump_to_previous_sibling_comment(),
-                CommentsKeyAction::JumpNextSibling => self.jump_to_next_sibling_comment(),
-                CommentsKeyAction::ScrollUp => self.scroll_comments_up(1),
-                CommentsKeyAction::ScrollDown => self.scroll_comments_down(1),
-                CommentsKeyAction::ScrollPageUp => {
-                    self.scroll_comments_up(self.comment_page_step())
-                }
-                CommentsKeyAction::ScrollPageDown => {
-                    self.scroll_comments_down(self.comment_page_step())
-                }
-                CommentsKeyAction::ScrollHome => self.comments_scroll = 0,
-                CommentsKeyAction::ScrollEnd => self.comments_scroll = self.max_comment_scroll(),
-            }
+    fn toggle_follow(&mut self, username: &str) {
+        let followed = self.saved_state.toggle_follow(username);
+        self.status_message = Some(if followed {
+            format!("Following {username}")
+        } else {
+            format!("Unfollowed {username}")
+        });
+        // The cached Following feed no longer matches the followed users.
+        self.feed_cache.remove(&FeedTab::Following);
+        if self.selected_feed == FeedTab::Following {
+            self.events.send(AppEvent::Refresh);
         }
+        self.persist_state();
     }
 
-    fn handle_bookmarks_key(&mut self, key_code: KeyCode) {
-        if let Some(action) = map_bookmarks_action(key_code, self.bookmarks_collapsed) {
-            match action {
-                BookmarksKeyAction::Expand => self.open_bookmarks_pane(),
-                BookmarksKeyAction::Close => self.close_bookmarks_pane(),
-                BookmarksKeyAction::BookmarkSelected => self.bookmark_selected_post(),
-                BookmarksKeyAction::SelectPrevious => self.select_previous_bookmark(),
-                BookmarksKeyAction::SelectNext => self.select_next_bookmark(),
-                BookmarksKeyAction::OpenComments => self.select_post_from_bookmark(),
-                BookmarksKeyAction::OpenPost => self.open_selected_bookmark(),
-                BookmarksKeyAction::OpenAll => self.open_all_bookmarks(),
-                BookmarksKeyAction::Delete => self.remove_selected_bookmark(),
-            }
+    fn mute_author(&mut self, author: &str) {
+        if !self.saved_state.mutes.mute_author(author) {
+            self.status_message = Some(format!("{author} is already muted"));
+            return;
         }
+        self.status_message = Some(format!("Muted {author}"));
+        self.apply_mute_rules();
     }
 
-    fn exit(&mut self) {
-        self.running = false;
+    fn mute_domain_of(&mut self, post: &Post) {
+        let domain = post.domain.as_str();
+        if domain.is_empty() || domain == SELF_POST_DOMAIN {
+            self.status_message = Some("This post has no domain to mute".to_string());
+            return;
+        }
+        if !self.saved_state.mutes.mute_domain(domain) {
+            self.status_message = Some(format!("{domain} is already muted"));
+            return;
+        }
+        self.status_message = Some(format!("Muted {domain}"));
+        self.apply_mute_rules();
     }
 
-    fn handle_app_event(&mut self, event: AppEvent) {
-        match event {
-            AppEvent::Quit => self.exit(),
-            AppEvent::Refresh => {
-                self.posts_notice = None;
-                self.reset_comments_state();
-                self.refresh_posts();
-            }
-            AppEvent::PostsFetched { request_id, result } => {
-                self.handle_posts_fetched(request_id, result)
-            }
-            AppEvent::LoadCommentsComplete { post_id, result } => {
-                if !self.comments_open || self.comments_for_post_id != Some(post_id) {
+    /// Rebuilds the filter from the saved rules, hides newly muted posts and
+    /// collapses newly muted comments, including cached ones.
+    fn apply_mute_rules(&mut self) {
+        let (filter, error) = MuteFilter::new(&self.saved_state.mutes);
+        self.mute_filter = filter;
+        if error.is_some() {
+            self.status_message = error;
+        }
+        self.persist_state();
+
+        let mutes = &self.mute_filter;
+        let is_visible = |post: &Post| !mutes.is_post_muted(&post.author, &post.title, &post.url);
+        self.feed_posts.retain(is_visible);
+        for cached in self.feed_cache.values_mut() {
+            cached.posts.retain(is_visible);
+            cached.selected_index = cached
+                .selected_index
+                .map(|index| index.min(cached.posts.len().saturating_sub(1)));
+        }
+
+        self.apply_posts_view();
+
+        let mutes = &self.mute_filter;
+        let mark_muted = |comment: &mut Comment| {
+            comment.muted = mutes.is_author_muted(&comment.author);
+        };
+        self.comments.iter_mut().for_each(mark_muted);
+        for cached in self.comments_cache.values_mut() {
+            cached.comments.iter_mut().for_each(mark_muted);
+        }
+    }
+
+    fn toggle_focused_comment_reveal(&mut self) {
+        let Some(comment) = self.focused_comment() else {
+            return;
+        };
+        if !comment.muted {
+            return;
+        }
+        let id = comment.id;
+        if let Some(index) = self.revealed_comment_ids.iter().position(|&rid| rid == id) {
+            self.revealed_comment_ids.remove(index);
+        } else {
+            self.revealed_comment_ids.push(id);
+        }
+    }
+
+    fn persist_state(&mut self) {
+        let Some(path) = self.state_path.as_deref() else {
+            return;
+        };
+        if let Err(error) = self.saved_state.save(path) {
+            self.status_message = Some(error);
+        }
+    }
+
+    fn run_palette_command(&mut self, command: PaletteCommand) {
+        match command {
+            PaletteCommand::Global(action) => self.run_global_action(action),
+            PaletteCommand::Posts(action) => {
+                self.set_focus_pane(Pane::Posts);
+                self.run_posts_action(action);
+            }
+            PaletteCommand::Comments(action) => {
+                if !self.comments_open {
+                    self.status_message = Some("No comments are open".to_string());
+                    return;
+                }
+                self.set_focus_pane(Pane::Comments);
+                self.run_comments_action(action);
+            }
+            PaletteCommand::Reader(action) => {
+                if self.reader.is_none() {
+                    self.status_message = Some("No article is open".to_string());
+                    return;
+                }
+                self.set_focus_pane(Pane::Reader);
+                self.run_reader_action(action);
+            }
+            PaletteCommand::Bookmarks(action) => {
+                if !self.bookmarks_visible() {
+                    self.status_message = Some("No bookmarks yet".to_string());
+                    return;
+                }
+                self.open_bookmarks_pane();
+                self.run_bookmarks_action(action);
+            }
+            PaletteCommand::Alerts(action) => {
+                if self.alerts.is_empty() {
+                    self.status_message = Some("No alerts yet".to_string());
                     return;
                 }
+                self.set_focus_pane(Pane::Alerts);
+                self.run_alerts_action(action);
+            }
+            PaletteCommand::SwitchFeed(feed) => self.switch_to_feed(feed),
+            PaletteCommand::OpenItem(item_id) => self.open_item_by_id(item_id),
+        }
+    }
 
-                self.comments_loading = false;
-                match result {
-                    Ok(comments) => {
-                        self.comments_cache.insert(
-                            post_id,
-                            CachedComments {
-                                comments: comments.clone(),
-                                fetched_at: Instant::now(),
-                            },
-                        );
-                        self.comments = comments;
-                        self.comments_error = None;
-                        self.comments_notice = None;
-                        self.comments_scroll = 0;
-                        self.comment_start_lines.clear();
-                    }
-                    Err(err) => {
-                        if self.comments_cache.contains_key(&post_id) {
-                            self.comments_error = None;
-                        } else {
-                            self.comments.clear();
-                            self.comments_error = Some(err);
-                            self.comments_notice = None;
-                            self.comment_start_lines.clear();
-                        }
+    fn handle_feeds_key(&mut self, key_code: KeyCode) {
+        if let Some(action) = map_feeds_action(key_code) {
+            match action {
+                FeedsKeyAction::SelectPrevious => self.select_previous_feed(),
+                FeedsKeyAction::SelectNext => self.select_next_feed(),
+                FeedsKeyAction::FocusPosts => self.set_focus_pane(Pane::Posts),
+            }
+        }
+    }
+
+    fn handle_pane_shortcut(&mut self, key: char) {
+        match key {
+            '1' => {
+                if !self.bookmarks_visible() {
+                    return;
+                }
+
+                if self.focus_pane == Pane::Bookmarks {
+                    if self.bookmarks_collapsed {
+                        self.open_bookmarks_pane();
+                    } else {
+                        self.bookmarks_collapsed = true;
                     }
+                } else {
+                    self.open_bookmarks_pane();
                 }
             }
-            AppEvent::OpenPost(url) => {
-                let _ = webbrowser::open(&url);
+            '2' => {
+                self.set_focus_pane(Pane::Posts);
+            }
+            '3' if self.comments_open => {
+                self.set_focus_pane(Pane::Comments);
+            }
+            '4' => {
+                self.set_focus_pane(Pane::Feeds);
+            }
+            '5' if self.reader.is_some() => {
+                self.set_focus_pane(Pane::Reader);
+            }
+            '6' if !self.alerts.is_empty() => {
+                self.set_focus_pane(Pane::Alerts);
             }
+            _ => {}
         }
+        self.ensure_focus_valid();
     }
 
-    fn on_tick(&mut self) -> bool {
-        if self.loading || (self.comments_open && self.comments_loading) {
-            self.loading_frame = self.loading_frame.wrapping_add(1);
-            return true;
+    fn handle_posts_key(&mut self, key_code: KeyCode) {
+        if let Some(action) = map_posts_action(key_code, self.comments_open) {
+            self.run_posts_action(action);
         }
+    }
 
-        false
+    fn run_posts_action(&mut self, action: PostsKeyAction) {
+        match action {
+            PostsKeyAction::SelectPrevious => self.select_previous(),
+            PostsKeyAction::SelectNextAndLoadMore => {
+                self.select_next();
+                self.load_more_posts();
+            }
+            PostsKeyAction::BookmarkSelected => self.bookmark_selected_post(),
+            PostsKeyAction::OpenComments => self.open_comments_for_selected(),
+            PostsKeyAction::OpenPost => self.open_selected_post(),
+            PostsKeyAction::OpenDiscussion => {
+                if let Some(id) = self.selected_post().map(|post| post.id) {
+                    self.events.send(AppEvent::OpenPost(discussion_url(id)));
+                }
+            }
+            PostsKeyAction::OpenReader => {
+                if let Some(post) = self.selected_post().cloned() {
+                    self.open_reader_for_post(&post);
+                }
+            }
+            PostsKeyAction::ViewAuthor => {
+                if let Some(author) = self.selected_post().map(|post| post.author.clone()) {
+                    self.open_profile(&author);
+                }
+            }
+            PostsKeyAction::CopyUrl => {
+                if let Some(url) = self.selected_post().map(|post| post.url.clone()) {
+                    self.copy_to_clipboard("post URL", url);
+                }
+            }
+            PostsKeyAction::CopyDiscussionUrl => {
+                if let Some(id) = self.selected_post().map(|post| post.id) {
+                    self.copy_to_clipboard("discussion URL", discussion_url(id));
+                }
+            }
+            PostsKeyAction::MuteAuthor => {
+                if let Some(author) = self.selected_post().map(|post| post.author.clone()) {
+                    self.mute_author(&author);
+                }
+            }
+            PostsKeyAction::MuteDomain => {
+                if let Some(post) = self.selected_post().cloned() {
+                    self.mute_domain_of(&post);
+                }
+            }
+            PostsKeyAction::FilterDomain => self.toggle_domain_filter(),
+            PostsKeyAction::CycleSort => {
+                self.update_posts_view(|view| view.sort = view.sort.next())
+            }
+            PostsKeyAction::CycleMinPoints => self.update_posts_view(PostsView::cycle_min_points),
+            PostsKeyAction::CycleMinComments => {
+                self.update_posts_view(PostsView::cycle_min_comments)
+            }
+            PostsKeyAction::CycleMaxAge => self.update_posts_view(PostsView::cycle_max_age),
+            PostsKeyAction::ToggleHideJobs => {
+                self.update_posts_view(|view| view.hide_jobs = !view.hide_jobs)
+            }
+            PostsKeyAction::ResetView => {
+                self.update_posts_view(|view| *view = PostsView::default())
+            }
+            PostsKeyAction::CloseComments => self.close_comments_view(),
+        }
     }
 
-    fn spinner_frame(&self) -> &'static str {
-        SPINNER_FRAMES[self.loading_frame % SPINNER_FRAMES.len()]
+    fn handle_comments_key(&mut self, key_code: KeyCode) {
+        if let Some(action) = map_comments_action(key_code) {
+            self.run_comments_action(action);
+        }
     }
 
-    fn current_hhmm() -> String {
-        Local::now().format("%H:%M:%S").to_string()
+    fn run_comments_action(&mut self, action: CommentsKeyAction) {
+        match action {
+            CommentsKeyAction::Close => self.close_comments_view(),
+            CommentsKeyAction::BookmarkPost => self.bookmark_comments_post(),
+            CommentsKeyAction::OpenPost => self.open_comments_post(),
+            CommentsKeyAction::OpenDiscussion => {
+                if let Some(id) = self.comments_post().map(|post| post.id) {
+                    self.events.send(AppEvent::OpenPost(discussion_url(id)));
+                }
+            }
+            CommentsKeyAction::OpenReader => {
+                if let Some(post) = self.comments_post().cloned() {
+                    self.open_reader_for_post(&post);
+                }
+            }
+            CommentsKeyAction::ViewAuthor => {
+                if let Some(author) = self.focused_comment().map(|comment| comment.author.clone()) {
+                    self.open_profile(&author);
+                }
+            }
+            CommentsKeyAction::OpenCommentLinks => self.open_link_picker(LinkScope::Comment),
+            CommentsKeyAction::OpenThreadLinks => self.open_link_picker(LinkScope::Thread),
+            CommentsKeyAction::CopyPermalink => {
+                if let Some(id) = self.focused_comment().map(|comment| comment.id) {
+                    self.copy_to_clipboard("comment link", discussion_url(id));
+                }
+            }
+            CommentsKeyAction::CopyText => {
+                if let Some(text) = self
+                    .focused_comment()
+                    .map(|comment| blocks_to_plain_text(&comment.body))
+                {
+                    self.copy_to_clipboard("comment text", text);
+                }
+            }
+            CommentsKeyAction::MuteAuthor => {
+                if let Some(author) = self.focused_comment().map(|comment| comment.author.clone()) {
+                    self.mute_author(&author);
+                }
+            }
+            CommentsKeyAction::MuteDomain => {
+                if let Some(post) = self.comments_post().cloned() {
+                    self.mute_domain_of(&post);
+                }
+            }
+            CommentsKeyAction::ToggleMuted => self.toggle_focused_comment_reveal(),
+            CommentsKeyAction::JumpPrevious => self.jump_to_previous_comment(),
+            CommentsKeyAction::JumpNext => self.jump_to_next_comment(),
+            CommentsKeyAction::JumpPreviousSibling => self.jump_to_previous_sibling_comment(),
+            CommentsKeyAction::JumpNextSibling => self.jump_to_next_sibling_comment(),
+            CommentsKeyAction::CycleOrder => self.cycle_comment_order(),
+            CommentsKeyAction::JumpNextOp => self.jump_to_next_op_comment(),
+            CommentsKeyAction::JumpParent => self.jump_from_focused_comment(nearest_parent_index),
+            CommentsKeyAction::JumpTopLevel => {
+                self.jump_from_focused_comment(top_level_ancestor_index)
+            }
+            CommentsKeyAction::JumpNextThread => match self.focused_comment_index() {
+                Some(_) => self.jump_from_focused_comment(next_top_level_index),
+                None => self.jump_to_comment(0),
+            },
+            CommentsKeyAction::ScrollUp => self.scroll_comments_up(1),
+            CommentsKeyAction::ScrollDown => self.scroll_comments_down(1),
+            CommentsKeyAction::ScrollPageUp => self.scroll_comments_up(self.comment_page_step()),
+            CommentsKeyAction::ScrollPageDown => {
+                self.scroll_comments_down(self.comment_page_step())
+            }
+            CommentsKeyAction::ScrollHome => self.comments_scroll = 0,
+            CommentsKeyAction::ScrollEnd => self.comments_scroll = self.max_comment_scroll(),
+        }
+        self.load_more_comments_if_near_end();
     }
 
-    fn begin_posts_request(&mut self) -> (u64, CancellationToken) {
-        if let Some(cancel_token) = self.posts_request_cancel.take() {
-            cancel_token.cancel();
+    fn handle_reader_key(&mut self, key_code: KeyCode) {
+        if let Some(action) = map_reader_action(key_code) {
+            self.run_reader_action(action);
         }
+    }
 
-        self.next_posts_request_id = self.next_posts_request_id.wrapping_add(1).max(1);
-        let request_id = self.next_posts_request_id;
-        self.active_posts_request_id = Some(request_id);
-        self.loading = true;
-        self.loading_frame = 0;
+    fn run_reader_action(&mut self, action: ReaderKeyAction) {
+        if action == ReaderKeyAction::Close {
+            self.close_reader();
+            return;
+        }
+        let Some(reader) = self.reader.as_mut() else {
+            return;
+        };
 
-        let cancel_token = CancellationToken::new();
-        self.posts_request_cancel = Some(cancel_token.clone());
-        (request_id, cancel_token)
+        match action {
+            ReaderKeyAction::Close => {}
+            ReaderKeyAction::OpenInBrowser => {
+                self.events.send(AppEvent::OpenPost(reader.url.clone()));
+            }
+            ReaderKeyAction::ScrollUp => reader.scroll_up(1),
+            ReaderKeyAction::ScrollDown => reader.scroll_down(1),
+            ReaderKeyAction::ScrollPageUp => reader.scroll_up(reader.page_step()),
+            ReaderKeyAction::ScrollPageDown => reader.scroll_down(reader.page_step()),
+            ReaderKeyAction::ScrollHome => reader.scroll = 0,
+            ReaderKeyAction::ScrollEnd => reader.scroll = reader.max_scroll(),
+        }
     }
 
-    fn refresh_posts(&mut self) {
-        let (request_id, cancel_token) = self.begin_posts_request();
-        if self.posts.is_empty() {
-            self.story_ids.clear();
-            self.next_story_index = 0;
-            self.has_more_posts = true;
-            self.list_state.select(None);
+    fn handle_bookmarks_key(&mut self, key_code: KeyCode) {
+        if let Some(action) = map_bookmarks_action(key_code, self.bookmarks_collapsed) {
+            self.run_bookmarks_action(action);
         }
-        self.posts_notice = None;
-        let feed = self.selected_feed.api_feed();
-
-        let client = self.hn_client.clone();
-        self.events.send_async(async move {
-            let result: Result<PostsFetchResult, String> = tokio::select! {
-                _ = cancel_token.cancelled() => Err("Cancelled".to_string()),
-                result = async {
-                    let story_ids = client.fetch_story_ids(feed).await?;
-                    let next_story_index = story_ids.len().min(POSTS_PAGE_SIZE);
-                    let page_ids: Vec<u64> = story_ids.iter().take(next_story_index).copied().collect();
-                    let items = client.fetch_items_by_ids(&page_ids, feed).await?;
+    }
 
-                    Ok(PostsFetchResult {
-                        mode: PostsFetchMode::Replace,
-                        story_ids: Some(story_ids),
-                        items,
-                        next_story_index,
-                    })
-                } => result.map_err(|e: reqwest::Error| e.to_string()),
-            };
+    fn run_bookmarks_action(&mut self, action: BookmarksKeyAction) {
+        match action {
+            BookmarksKeyAction::Expand => self.open_bookmarks_pane(),
+            BookmarksKeyAction::Close => self.close_bookmarks_pane(),
+            BookmarksKeyAction::BookmarkSelected => self.bookmark_selected_post(),
+            BookmarksKeyAction::SelectPrevious => self.select_previous_bookmark(),
+            BookmarksKeyAction::SelectNext => self.select_next_bookmark(),
+            BookmarksKeyAction::OpenComments => self.select_post_from_bookmark(),
+            BookmarksKeyAction::OpenPost => self.open_selected_bookmark(),
+            BookmarksKeyAction::OpenAll => self.open_all_bookmarks(),
+            BookmarksKeyAction::Delete => self.remove_selected_bookmark(),
+        }
+    }
 
-            AppEvent::PostsFetched { request_id, result }
-        });
+    fn handle_alerts_key(&mut self, key_code: KeyCode) {
+        if let Some(action) = map_alerts_action(key_code) {
+            self.run_alerts_action(action);
+        }
     }
 
-    fn request_more_posts(&mut self) {
-        if self.loading || !self.has_more_posts {
+    fn run_alerts_action(&mut self, action: AlertsKeyAction) {
+        let len = self.alerts.len();
+        if len == 0 {
             return;
         }
+        let selected = self.alerts_state.selected().unwrap_or(0).min(len - 1);
 
-        if self.next_story_index >= self.story_ids.len() {
-            self.has_more_posts = false;
-            return;
+        match action {
+            AlertsKeyAction::SelectPrevious => {
+                self.alerts_state.select(Some(selected.saturating_sub(1)));
+            }
+            AlertsKeyAction::SelectNext => {
+                self.alerts_state.select(Some((selected + 1).min(len - 1)));
+            }
+            AlertsKeyAction::OpenComments => {
+                let item_id = self.alerts[selected].item_id;
+                self.open_item_by_id(item_id);
+            }
+            AlertsKeyAction::OpenPost => {
+                let alert = &self.alerts[selected];
+                let url = if alert.url.is_empty() {
+                    discussion_url(alert.item_id)
+                } else {
+                    alert.url.clone()
+                };
+                self.events.send(AppEvent::OpenPost(url));
+            }
+            AlertsKeyAction::Dismiss => {
+                self.alerts.remove(selected);
+                let remaining = self.alerts.len();
+                self.alerts_state
+                    .select((remaining > 0).then(|| selected.min(remaining - 1)));
+            }
+            AlertsKeyAction::ClearAll => {
+                self.alerts.clear();
+                self.alerts_state.select(None);
+            }
         }
+        if self.alerts.is_empty() && self.focus_pane == Pane::Alerts {
+            self.set_focus_pane(Pane::Posts);
+        }
+    }
 
-        let (request_id, cancel_token) = self.begin_posts_request();
+    /// Kicks off a scan of every feed once the interval has passed, so watch
+    /// rules see stories outside the feed on screen.
+    fn start_watch_scan_if_due(&mut self) {
+        if self.watch_list.is_empty() || self.watch_scan_running {
+            return;
+        }
+        if self.watch_scan_started_at.is_some_and(|started| {
+            started.elapsed() < Duration::from_secs(WATCH_SCAN_INTERVAL_SECS)
+        }) {
+            return;
+        }
 
-        let start = self.next_story_index;
-        let next_story_index = start
-            .saturating_add(POSTS_PAGE_SIZE)
-            .min(self.story_ids.len());
-        let page_ids: Vec<u64> = self.story_ids[start..next_story_index].to_vec();
-        let feed = self.selected_feed.api_feed();
+        self.watch_scan_running = true;
+        self.watch_scan_started_at = Some(Instant::now());
         let client = self.hn_client.clone();
-
         self.events.send_async(async move {
-            let result: Result<PostsFetchResult, String> = tokio::select! {
-                _ = cancel_token.cancelled() => Err("Cancelled".to_string()),
-                result = client.fetch_items_by_ids(&page_ids, feed) => {
-                    result
-                        .map(|items| PostsFetchResult {
-                            mode: PostsFetchMode::Append,
-                            story_ids: None,
-                            items,
-                            next_story_index,
-                        })
-                        .map_err(|e| e.to_string())
-                },
-            };
-
-            AppEvent::PostsFetched { request_id, result }
+            let result = client
+                .fetch_feed_heads(WATCH_SCAN_ITEMS_PER_FEED)
+                .await
+                .map(|fetched| fetched.items);
+            AppEvent::WatchScanFinished { result }
         });
     }
 
-    fn handle_posts_fetched(&mut self, request_id: u64, result: Result<PostsFetchResult, String>) {
-        if self.active_posts_request_id != Some(request_id) {
+    /// Adds an alert for each new item matching a watch rule, newest first.
+    fn record_alerts(&mut self, items: &[Item]) {
+        if self.watch_list.is_empty() {
             return;
         }
 
-        self.loading = false;
-        self.active_posts_request_id = None;
-        self.posts_request_cancel = None;
-
-        match result {
-            Ok(payload) => {
-                self.posts_notice = None;
+        let mut new_alerts: Vec<Alert> = Vec::new();
+        for item in items {
+            if self.alerted_item_ids.contains(&item.id) {
+                continue;
+            }
+            let Some(rule) = self.watch_list.matching_rule(item) else {
+                continue;
+            };
+            let alert = Alert::new(item, rule);
+            let author = item.by.as_deref().unwrap_or_default();
+            if self
+                .mute_filter
+                .is_post_muted(author, &alert.title, &alert.url)
+            {
+                continue;
+            }
+            self.alerted_item_ids.insert(item.id);
+            new_alerts.push(alert);
+        }
+        if new_alerts.is_empty() {
+            return;
+        }
 
-                if let Some(story_ids) = payload.story_ids {
-                    self.story_ids = story_ids;
-                }
+        self.status_message = Some(match new_alerts.as_slice() {
+            [alert] => format!("Watch alert: {}", alert.title),
+            alerts => format!("{} new watch alerts", alerts.len()),
+        });
+        let added = new_alerts.len();
+        new_alerts.append(&mut self.alerts);
+        new_alerts.truncate(MAX_ALERTS);
+        self.alerts = new_alerts;
+        let selected = self
+            .alerts_state
+            .selected()
+            .map_or(0, |index| index + added)
+            .min(self.alerts.len() - 1);
+        self.alerts_state.select(Some(selected));
+    }
 
-                self.next_story_index = payload.next_story_index;
-                let incoming_posts = Self::posts_from_items(payload.items);
+    fn exit(&mut self) {
+        self.running = false;
+    }
 
-                match payload.mode {
-                    PostsFetchMode::Replace => {
-                        self.posts = incoming_posts;
-                    }
-                    PostsFetchMode::Append => {
-                        self.posts.extend(incoming_posts);
-                    }
+    fn handle_app_event(&mut self, event: AppEvent) {
+        match event {
+            AppEvent::Quit => self.exit(),
+            AppEvent::Refresh => {
+                self.posts_notice = None;
+                self.reset_comments_state();
+                self.refresh_posts();
+            }
+            AppEvent::WatchScanFinished { result } => {
+                self.watch_scan_running = false;
+                // Background scans stay quiet on failure; the next interval retries.
+                if let Ok(items) = result {
+                    self.record_alerts(&items);
                 }
-                self.last_fetched = Some(Self::current_hhmm());
-
-                self.has_more_posts = self.next_story_index < self.story_ids.len();
-
-                if self.posts.is_empty() {
-                    self.list_state.select(None);
-                } else {
-                    let selected = self.list_state.selected().unwrap_or(0);
-                    let max_index = self.posts.len().saturating_sub(1);
-                    self.list_state.select(Some(selected.min(max_index)));
+            }
+            AppEvent::PostsFetched { request_id, result } => {
+                self.handle_posts_fetched(request_id, result)
+            }
+            AppEvent::LoadCommentsComplete { post_id, result } => {
+                if !self.comments_open || self.comments_for_post_id != Some(post_id) {
+                    return;
                 }
 
-                self.cache_current_feed();
+                self.comments_loading = false;
+                match result {
+                    Ok(page) => {
+                        let CommentsPage { comments, thread } = *page;
+                        let replaces_cache = self.comments_cache.contains_key(&post_id);
+                        self.comments_cache.insert(
+                            post_id,
+                            CachedComments {
+                                comments: comments.clone(),
+                                thread: Some(thread.clone()),
+                                fetched_at: Instant::now(),
+                            },
+                        );
+                        self.comments = self.arrange_comments(comments, &thread);
+                        self.comments_thread = Some(thread);
+                        self.comments_error = None;
+                        self.comments_notice = None;
+                        self.comment_start_lines.clear();
+                        // Streamed comments are already on screen; only jump back to the
+                        // top when a previously cached thread is being replaced, and
+                        // leave the first comment for a poll that arrived with the thread.
+                        if replaces_cache {
+                            self.comments_scroll = 0;
+                            self.comment_cursor = self.initial_comment_cursor();
+                        } else if self.comment_cursor == Some(0) {
+                            self.comment_cursor = self.initial_comment_cursor();
+                        }
+                    }
+                    Err(err) => {
+                        if self.comments_cache.contains_key(&post_id) {
+                            self.comments_error = None;
+                        } else {
+                            self.comments.clear();
+                            self.comments_error = Some(err.describe());
+                            self.comments_notice = None;
+                            self.comment_start_lines.clear();
+                        }
+                    }
+                }
             }
-            Err(err) => {
-                if err == "Cancelled" {
-                    return;
+            AppEvent::CommentsProgress { post_id, comments } => {
+                self.handle_comments_progress(post_id, comments)
+            }
+            AppEvent::MoreCommentsLoaded { post_id, page } => {
+                self.handle_more_comments_loaded(post_id, page)
+            }
+            AppEvent::ItemFetched { item_id, result } => self.handle_item_fetched(item_id, result),
+            AppEvent::UserProfileFetched { username, result } => {
+                if let Some(view) = self
+                    .profile
+                    .as_mut()
+                    .filter(|view| view.username() == username)
+                {
+                    view.set_result(
+                        result
+                            .map(|profile| profile.map(|profile| *profile))
+                            .map_err(|err| err.describe()),
+                    );
                 }
-                if self.posts.is_empty() {
-                    self.posts_notice = Some(format!("Failed to load posts: {err}"));
+            }
+            AppEvent::ArticleFetched { url, result } => self.handle_article_fetched(url, result),
+            AppEvent::OpenPost(url) => self.open_url(url),
+            AppEvent::OpenFinished { url, result } => {
+                if let Err(error) = result {
+                    self.status_message = Some(format!("Failed to open {url}: {error}"));
                 }
             }
+            AppEvent::CopyFinished { what, result } => self.handle_copy_finished(&what, result),
         }
     }
 
-    fn has_reached_load_more_threshold(&self) -> bool {
-        let len = self.posts.len();
-        if len == 0 {
-            return self.has_more_posts;
+    fn handle_comments_progress(&mut self, post_id: u64, comments: Vec<Comment>) {
+        if !self.comments_open || self.comments_for_post_id != Some(post_id) {
+            return;
         }
 
-        let Some(selected_index) = self.list_state.selected() else {
-            return false;
-        };
-
-        let threshold_count = len
-            .saturating_mul(LOAD_MORE_TRIGGER_NUMERATOR)
-            .saturating_add(LOAD_MORE_TRIGGER_DENOMINATOR - 1)
-            / LOAD_MORE_TRIGGER_DENOMINATOR;
-
-        selected_index.saturating_add(1) >= threshold_count.max(1)
-    }
-
-    fn load_more_posts(&mut self) {
-        if self.loading || self.comments_open || !self.has_more_posts {
+        // A stale cached thread stays visible until its refresh completes.
+        let refreshing_cache = self.comments_loading && self.comments_cache.contains_key(&post_id);
+        let is_loading = self.comments_loading || self.comments_loading_more;
+        if !is_loading || refreshing_cache || comments.len() <= self.comments.len() {
             return;
         }
-
-        if !self.has_reached_load_more_threshold() {
+        // Progress is the ranked prefix; other orders wait for the whole page.
+        if self.comment_order != CommentOrder::Ranked {
             return;
         }
 
-        self.request_more_posts();
+        let focused_id = self.focused_comment().map(|comment| comment.id);
+        self.comments = comments;
+        if let Some(id) = focused_id {
+            self.comment_cursor = self.comments.iter().position(|comment| comment.id == id);
+        }
     }
 
-    fn posts_from_items(items: Vec<Item>) -> Vec<Post> {
-        items
-            .into_iter()
-            .filter_map(|item| {
-                if item.dead || item.deleted {
-                    return None;
-                }
-
-                let post_type = PostType::from_kind(item.kind.as_deref())?;
-                let title = item.title?;
-                let url = item.url?;
+    fn handle_more_comments_loaded(&mut self, post_id: u64, page: Box<CommentsPage>) {
+        if !self.comments_open || self.comments_for_post_id != Some(post_id) {
+            return;
+        }
 
-                Some(Post {
-                    id: item.id,
-                    title,
-                    url,
-                    post_type,
-                    points: item.score.unwrap_or_default(),
-                    comments: item.descendants.unwrap_or_default(),
-                    author: item
-                        .by
-                        .filter(|author| !author.is_empty())
-                        .unwrap_or_else(|| "unknown".to_string()),
-                    published_at: item.time.unwrap_or_default(),
-                })
-            })
-            .collect()
+        self.comments_loading_more = false;
+        if let Some(cached) = self.comments_cache.get_mut(&post_id) {
+            cached.comments = page.comments.clone();
+            cached.thread = Some(page.thread.clone());
+        }
+        let focused_id = self.focused_comment().map(|comment| comment.id);
+        self.comments = self.arrange_comments(page.comments, &page.thread);
+        self.comments_thread = Some(page.thread);
+        self.comment_cursor =
+            focused_id.and_then(|id| self.comments.iter().position(|comment| comment.id == id));
     }
 
-    fn cache_current_feed(&mut self) {
-        self.feed_cache.insert(
-            self.selected_feed,
-            CachedFeed {
-                story_ids: self.story_ids.clone(),
-                next_story_index: self.next_story_index,
-                has_more_posts: self.has_more_posts,
-                posts: self.posts.clone(),
-                selected_index: self.list_state.selected(),
-                last_fetched: self.last_fetched.clone(),
-            },
-        );
+    fn failed_comment_count(&self) -> usize {
+        self.comments_thread
+            .as_ref()
+            .map_or(0, CommentThread::failed_count)
     }
 
-    fn restore_feed_from_cache(&mut self, feed: FeedTab) -> bool {
-        let Some(cached) = self.feed_cache.get(&feed).cloned() else {
-            return false;
-        };
-
-        self.story_ids = cached.story_ids;
-        self.next_story_index = cached.next_story_index;
-        self.has_more_posts = cached.has_more_posts;
-        self.posts = cached.posts;
-        self.last_fetched = cached.last_fetched;
-        self.posts_notice = None;
-
-        if self.posts.is_empty() {
-            self.list_state.select(None);
-        } else {
-            let selected = cached
-                .selected_index
-                .unwrap_or(0)
-                .min(self.posts.len().saturating_sub(1));
-            self.list_state.select(Some(selected));
+    /// Fetches the comments that failed to load again, keeping the loaded ones.
+    fn retry_failed_comments(&mut self) {
+        if self.comments_loading || self.comments_loading_more {
+            return;
         }
+        let Some(post_id) = self.comments_for_post_id else {
+            return;
+        };
+        let Some(mut thread) = self.comments_thread.clone() else {
+            return;
+        };
 
-        true
+        let retried = thread.retry_failed();
+        self.status_message = Some(format!("Retrying failed comments ({retried})"));
+        let limit = self.comments.len() + retried;
+        self.request_more_comments(post_id, thread, limit);
     }
 
-    fn clear_feed_state(&mut self) {
-        self.story_ids.clear();
-        self.next_story_index = 0;
-        self.has_more_posts = true;
-        self.posts.clear();
-        self.posts_notice = None;
-        self.last_fetched = None;
-        self.list_state.select(None);
+    /// `ranked` rearranged for the selected comment order.
+    fn arrange_comments(&self, ranked: Vec<Comment>, thread: &CommentThread) -> Vec<Comment> {
+        match self.comment_order {
+            CommentOrder::Ranked => ranked,
+            order => thread.comments(order, &self.mute_filter),
+        }
     }
 
-    fn select_next_feed(&mut self) {
-        self.switch_feed(1);
-    }
+    fn cycle_comment_order(&mut self) {
+        self.comment_order = self.comment_order.next();
+        self.status_message = Some(format!("Comments: {}", self.comment_order.label()));
+        let Some(thread) = self.comments_thread.as_ref() else {
+            return;
+        };
 
-    fn select_previous_feed(&mut self) {
-        self.switch_feed(-1);
+        self.comments = thread.comments(self.comment_order, &self.mute_filter);
+        self.comments_scroll = 0;
+        self.comment_cursor = self.initial_comment_cursor();
+        self.comment_start_lines.clear();
     }
 
-    fn switch_feed(&mut self, delta: isize) {
-        let count = FeedTab::ALL.len() as isize;
-        let current = self.selected_feed.index() as isize;
-        let next_index = (current + delta + count) % count;
-        let next_feed = FeedTab::from_index(next_index as usize);
-        self.switch_to_feed(next_feed);
-    }
+    fn handle_article_fetched(&mut self, url: String, result: Result<Box<Article>, String>) {
+        if let Ok(article) = &result {
+            self.cache_article(url.clone(), (**article).clone());
+        }
 
-    fn switch_to_feed(&mut self, next_feed: FeedTab) {
-        if next_feed == self.selected_feed {
+        let Some(reader) = self.reader.as_mut().filter(|reader| reader.url == url) else {
             return;
+        };
+        match result {
+            Ok(article) => reader.article = Some(*article),
+            Err(error) => reader.error = Some(error),
         }
+    }
 
-        self.cache_current_feed();
-        self.selected_feed = next_feed;
-        if !self.restore_feed_from_cache(next_feed) {
-            self.clear_feed_state();
+    fn cache_article(&mut self, url: String, article: Article) {
+        self.article_cache.retain(|(cached, _)| *cached != url);
+        if self.article_cache.len() >= MAX_CACHED_ARTICLES {
+            self.article_cache.remove(0);
         }
-        self.events.send(AppEvent::Refresh);
+        self.article_cache.push((url, article));
     }
 
-    fn select_next(&mut self) {
-        let len = self.posts.len();
-        if len == 0 {
-            self.list_state.select(None);
+    fn cached_article(&mut self, url: &str) -> Option<Article> {
+        let index = self
+            .article_cache
+            .iter()
+            .position(|(cached, _)| cached == url)?;
+        let entry = self.article_cache.remove(index);
+        let article = entry.1.clone();
+        self.article_cache.push(entry);
+        Some(article)
+    }
+
+    fn handle_item_fetched(&mut self, item_id: u64, result: Result<Box<Item>, FetchError>) {
+        if !self.comments_open || self.comments_for_post_id != Some(item_id) {
             return;
         }
 
-        let next = match self.list_state.selected() {
-            Some(i) if i + 1 < len => i + 1,
-            _ => 0,
+        self.comments_loading = false;
+        let item = match result {
+            Ok(item) => *item,
+            Err(err) => {
+                self.comments_error = Some(err.describe());
+                return;
+            }
+        };
+        let kind = item.kind.clone();
+        // Items opened explicitly by ID are shown even when muted.
+        let Some(post) = Self::posts_from_items(vec![item], &MuteFilter::default())
+            .into_iter()
+            .next()
+        else {
+            self.comments_notice = Some(match kind {
+                Some(kind) if PostType::from_kind(Some(&kind)).is_some() => {
+                    format!("Item {item_id} is a deleted or untitled {kind}.")
+                }
+                Some(kind) => format!("Item {item_id} is a {kind}, not a story, job or poll."),
+                None => format!("Item {item_id} is not a story, job or poll."),
+            });
+            return;
         };
 
-        self.list_state.select(Some(next));
+        let post_type = post.post_type;
+        self.viewed_post = Some(post);
+        self.load_comments(item_id, post_type);
     }
 
-    fn select_previous(&mut self) {
-        let len = self.posts.len();
-        if len == 0 {
-            self.list_state.select(None);
-            return;
+    fn on_tick(&mut self) -> bool {
+        self.start_watch_scan_if_due();
+        if self.loading
+            || (self.comments_open && (self.comments_loading || self.comments_loading_more))
+            || self.reader.as_ref().is_some_and(ReaderView::loading)
+            || self.profile.as_ref().is_some_and(ProfileView::is_loading)
+        {
+            self.loading_frame = self.loading_frame.wrapping_add(1);
+            return true;
         }
 
-        let prev = match self.list_state.selected() {
-            Some(0) | None => len - 1,
-            Some(i) => i - 1,
-        };
-
-        self.list_state.select(Some(prev));
+        false
     }
 
-    fn selected_post(&self) -> Option<&Post> {
-        let index = self.list_state.selected()?;
-        self.posts.get(index)
+    fn spinner_frame(&self) -> &'static str {
+        SPINNER_FRAMES[self.loading_frame % SPINNER_FRAMES.len()]
     }
 
-    fn post_by_id(&self, post_id: u64) -> Option<&Post> {
-        self.posts.iter().find(|post| post.id == post_id)
+    fn current_hhmm() -> String {
+        Local::now().format("%H:%M:%S").to_string()
     }
 
-    fn comments_post(&self) -> Option<&Post> {
-        self.comments_for_post_id
-            .and_then(|post_id| self.post_by_id(post_id))
+    fn unix_now() -> u64 {
+        Local::now().timestamp().max(0) as u64
     }
 
-    fn bookmark_post(&mut self, post: Post) {
-        if self.bookmarks.iter().any(|bookmark| bookmark.id == post.id) {
-            return;
+    fn begin_posts_request(&mut self) -> (u64, CancellationToken) {
+        if let Some(cancel_token) = self.posts_request_cancel.take() {
+            cancel_token.cancel();
         }
 
-        let was_empty = self.bookmarks.is_empty();
-        self.bookmarks.push(post);
-        if was_empty {
-            self.bookmarks_collapsed = true;
-        }
-        self.ensure_bookmarks_selection();
-    }
+        self.next_posts_request_id = self.next_posts_request_id.wrapping_add(1).max(1);
+        let request_id = self.next_posts_request_id;
+        self.active_posts_request_id = Some(request_id);
+        self.loading = true;
+        self.loading_frame = 0;
 
-    fn bookmark_selected_post(&mut self) {
-        let Some(post) = self.selected_post().cloned() else {
-            return;
-        };
-        self.bookmark_post(post);
+        let cancel_token = CancellationToken::new();
+        self.posts_request_cancel = Some(cancel_token.clone());
+        (request_id, cancel_token)
     }
 
-    fn bookmark_comments_post(&mut self) {
-        let Some(post) = self.comments_post().cloned() else {
+    fn refresh_posts(&mut self) {
+        let followed_users = self.saved_state.followed_users.clone();
+        let feed = self.selected_feed.api_feed();
+        if feed.is_none() && followed_users.is_empty() {
+            if let Some(cancel_token) = self.posts_request_cancel.take() {
+                cancel_token.cancel();
+            }
+            self.active_posts_request_id = None;
+            self.loading = false;
+            self.clear_feed_state();
+            self.has_more_posts = false;
+            self.posts_notice = Some(NO_FOLLOWED_USERS_NOTICE.to_string());
             return;
-        };
-        self.bookmark_post(post);
-    }
-
-    fn selected_bookmark(&self) -> Option<&Post> {
-        let index = self.bookmarks_state.selected()?;
-        self.bookmarks.get(index)
-    }
+        }
 
-    fn open_selected_bookmark(&mut self) {
-        let Some(bookmark) = self.selected_bookmark() else {
-            return;
-        };
-        self.events.send(AppEvent::OpenPost(bookmark.url.clone()));
-    }
-
-    fn open_all_bookmarks(&mut self) {
-        if self.bookmarks.is_empty() {
-            return;
+        let (request_id, cancel_token) = self.begin_posts_request();
+        if self.posts.is_empty() {
+            self.story_ids.clear();
+            self.next_story_index = 0;
+            self.has_more_posts = true;
+            self.list_state.select(None);
         }
+        self.posts_notice = None;
 
-        for bookmark in &self.bookmarks {
-            self.events.send(AppEvent::OpenPost(bookmark.url.clone()));
-        }
-    }
+        let client = self.hn_client.clone();
+        self.events.send_async(async move {
+            let result: Result<PostsFetchResult, FetchError> = tokio::select! {
+                _ = cancel_token.cancelled() => Err(FetchError::Cancelled),
+                result = async {
+                    let (story_ids, mut failed) = match feed {
+                        Some(feed) => (client.fetch_story_ids(feed).await?, Vec::new()),
+                        None => {
+                            let following = client
+                                .fetch_following_story_ids(
+                                    &followed_users,
+                                    FOLLOWING_SUBMISSIONS_PER_USER,
+                                )
+                                .await?;
+                            (following.story_ids, following.failed)
+                        }
+                    };
+                    let next_story_index = story_ids.len().min(POSTS_PAGE_SIZE);
+                    let page_ids: Vec<u64> = story_ids.iter().take(next_story_index).copied().collect();
+                    let fetched = client.fetch_items_by_ids(&page_ids).await?;
+                    failed.extend(fetched.failed);
 
-    fn select_post_from_bookmark(&mut self) {
-        let Some(bookmark_id) = self.selected_bookmark().map(|bookmark| bookmark.id) else {
-            return;
-        };
-        let Some(post_index) = self.posts.iter().position(|post| post.id == bookmark_id) else {
-            return;
-        };
+                    Ok(PostsFetchResult {
+                        mode: PostsFetchMode::Replace,
+                        story_ids: Some(story_ids),
+                        items: fetched.items,
+                        failed,
+                        next_story_index,
+                    })
+                } => result,
+            };
 
-        self.list_state.select(Some(post_index));
-        self.open_comments_for_selected();
+            AppEvent::PostsFetched { request_id, result }
+        });
     }
 
-    fn open_selected_post(&mut self) {
-        let Some(url) = self.selected_post().map(|post| post.url.clone()) else {
+    fn request_more_posts(&mut self) {
+        if self.loading || !self.has_more_posts {
             return;
-        };
-        self.events.send(AppEvent::OpenPost(url));
-    }
+        }
 
-    fn open_comments_post(&mut self) {
-        let Some(url) = self.comments_post().map(|post| post.url.clone()) else {
+        if self.next_story_index >= self.story_ids.len() {
+            self.has_more_posts = false;
             return;
-        };
-        self.events.send(AppEvent::OpenPost(url));
+        }
+
+        let start = self.next_story_index;
+        let next_story_index = start
+            .saturating_add(POSTS_PAGE_SIZE)
+            .min(self.story_ids.len());
+        let page_ids: Vec<u64> = self.story_ids[start..next_story_index].to_vec();
+        self.request_posts_by_ids(page_ids, PostsFetchMode::Append, next_story_index);
     }
 
-    fn open_comments_for_selected(&mut self) {
-        let Some((post_id, post_type)) = self.selected_post().map(|post| (post.id, post.post_type))
-        else {
+    /// Fetches the stories that failed to load again, keeping the loaded pages.
+    fn retry_failed_posts(&mut self) {
+        if self.loading {
             return;
-        };
+        }
 
-        self.set_focus_pane(Pane::Comments);
-        self.comments_open = true;
-        self.comments_scroll = 0;
-        self.comments_viewport_height = 0;
-        self.comment_line_count = 0;
-        self.comment_start_lines.clear();
-        self.load_comments(post_id, post_type);
+        let failed_ids = self.failed_items.clone();
+        self.status_message = Some(format!("Retrying failed posts ({})", failed_ids.len()));
+        // Following activity that failed to load has no story to refetch yet, so
+        // the feed is rebuilt instead.
+        if failed_ids.iter().any(|id| !self.story_ids.contains(id)) {
+            self.refresh_posts();
+            return;
+        }
+        self.request_posts_by_ids(
+            failed_ids,
+            PostsFetchMode::RetryFailed,
+            self.next_story_index,
+        );
     }
 
-    fn close_comments_view(&mut self) {
-        self.set_focus_pane(Pane::Posts);
-        self.reset_comments_state();
-    }
+    fn request_posts_by_ids(
+        &mut self,
+        ids: Vec<u64>,
+        mode: PostsFetchMode,
+        next_story_index: usize,
+    ) {
+        let (request_id, cancel_token) = self.begin_posts_request();
+        let client = self.hn_client.clone();
 
-    fn reset_comments_state(&mut self) {
-        self.comments_open = false;
-        self.comments.clear();
-        self.comments_for_post_id = None;
-        self.comments_loading = false;
-        self.comments_error = None;
-        self.comments_notice = None;
-        self.comments_scroll = 0;
-        self.comments_viewport_height = 0;
-        self.comment_line_count = 0;
-        self.comment_start_lines.clear();
-    }
+        self.events.send_async(async move {
+            let result: Result<PostsFetchResult, FetchError> = tokio::select! {
+                _ = cancel_token.cancelled() => Err(FetchError::Cancelled),
+                result = client.fetch_items_by_ids(&ids) => {
+                    result
+                        .map(|fetched| PostsFetchResult {
+                            mode,
+                            story_ids: None,
+                            items: fetched.items,
+                            failed: fetched.failed,
+                            next_story_index,
+                        })
+                },
+            };
 
-    fn load_comments(&mut self, post_id: u64, post_type: PostType) {
-        self.comments_for_post_id = Some(post_id);
-        self.comments_error = None;
-        self.comments_notice = None;
-        self.comments_loading = false;
-        self.comment_start_lines.clear();
+            AppEvent::PostsFetched { request_id, result }
+        });
+    }
 
-        if post_type == PostType::Job {
-            self.comments.clear();
-            self.comments_notice = Some("Jobs do not have comment threads.".to_string());
+    fn handle_posts_fetched(
+        &mut self,
+        request_id: u64,
+        result: Result<PostsFetchResult, FetchError>,
+    ) {
+        if self.active_posts_request_id != Some(request_id) {
             return;
         }
 
-        let should_refresh = if let Some(cached) = self.comments_cache.get(&post_id) {
-            self.comments = cached.comments.clone();
-            cached.fetched_at.elapsed() >= Duration::from_secs(COMMENTS_CACHE_REFRESH_AFTER_SECS)
-        } else {
-            self.comments.clear();
-            true
-        };
+        self.loading = false;
+        self.active_posts_request_id = None;
+        self.posts_request_cancel = None;
 
-        if !should_refresh {
-            return;
-        }
+        match result {
+            Ok(payload) => {
+                self.posts_notice = None;
 
-        self.comments_loading = true;
+                if let Some(story_ids) = payload.story_ids {
+                    self.story_ids = story_ids;
+                }
 
-        let client = self.hn_client.clone();
-        self.events.send_async(async move {
-            let result = client
-                .fetch_comments(post_id, 75)
-                .await
-                .map_err(|e| e.to_string());
-            AppEvent::LoadCommentsComplete { post_id, result }
-        });
-    }
+                self.next_story_index = payload.next_story_index;
+                self.record_alerts(&payload.items);
+                let incoming_posts = Self::posts_from_items(payload.items, &self.mute_filter);
 
-    fn max_comment_scroll(&self) -> u16 {
-        self.comment_line_count
-            .saturating_sub(self.comments_viewport_height) as u16
-    }
+                match payload.mode {
+                    PostsFetchMode::Replace => {
+                        self.feed_posts = incoming_posts;
+                        self.failed_items = payload.failed;
+                    }
+                    PostsFetchMode::Append => {
+                        self.feed_posts.extend(incoming_posts);
+                        self.failed_items.extend(payload.failed);
+                    }
+                    PostsFetchMode::RetryFailed => {
+                        self.feed_posts.extend(incoming_posts);
+                        self.failed_items = payload.failed;
+                        // Recovered posts go back to their rank in the feed.
+                        let ranks: HashMap<u64, usize> = self
+                            .story_ids
+                            .iter()
+                            .enumerate()
+                            .map(|(rank, id)| (*id, rank))
+                            .collect();
+                        self.feed_posts
+                            .sort_by_key(|post| ranks.get(&post.id).copied().unwrap_or(usize::MAX));
+                    }
+                }
+                self.last_fetched = Some(Self::current_hhmm());
 
-    fn comment_page_step(&self) -> u16 {
-        self.comments_viewport_height.saturating_sub(1).max(1) as u16
-    }
+                self.has_more_posts = self.next_story_index < self.story_ids.len();
+                self.apply_posts_view();
 
-    fn clamp_comments_scroll(&mut self) {
-        let max_scroll = self.max_comment_scroll();
-        if self.comments_scroll > max_scroll {
-            self.comments_scroll = max_scroll;
+                self.cache_current_feed();
+            }
+            Err(FetchError::Cancelled) => {}
+            Err(err) => {
+                let message = format!("Failed to load posts: {}", err.describe());
+                if self.posts.is_empty() {
+                    self.posts_notice = Some(message);
+                } else {
+                    self.status_message = Some(message);
+                }
+            }
         }
     }
 
-    fn scroll_comments_up(&mut self, amount: u16) {
-        self.comments_scroll = self.comments_scroll.saturating_sub(amount);
-    }
-
-    fn scroll_comments_down(&mut self, amount: u16) {
-        let max_scroll = self.max_comment_scroll();
-        self.comments_scroll = self.comments_scroll.saturating_add(amount).min(max_scroll);
-    }
+    fn has_reached_load_more_threshold(&self) -> bool {
+        let len = self.posts.len();
+        if len == 0 {
+            return self.has_more_posts;
+        }
 
-    fn jump_to_next_sibling_comment(&mut self) {
-        let Some(current_index) = current_index_from_scroll(
-            &self.comment_start_lines,
-            self.comments.len(),
-            self.comments_scroll,
-        ) else {
-            return;
+        let Some(selected_index) = self.list_state.selected() else {
+            return false;
         };
 
-        if let Some(next_index) = next_sibling_or_outer_index(&self.comments, current_index) {
-            self.jump_to_comment(next_index);
-        }
+        let threshold_count = len
+            .saturating_mul(LOAD_MORE_TRIGGER_NUMERATOR)
+            .saturating_add(LOAD_MORE_TRIGGER_DENOMINATOR - 1)
+            / LOAD_MORE_TRIGGER_DENOMINATOR;
+
+        selected_index.saturating_add(1) >= threshold_count.max(1)
     }
 
-    fn jump_to_previous_sibling_comment(&mut self) {
-        let Some(current_index) = current_index_from_scroll(
-            &self.comment_start_lines,
-            self.comments.len(),
-            self.comments_scroll,
-        ) else {
+    fn load_more_posts(&mut self) {
+        if self.loading || self.comments_open || !self.has_more_posts {
             return;
-        };
-
-        if let Some(parent_index) = previous_sibling_or_parent_index(&self.comments, current_index)
-        {
-            self.jump_to_comment(parent_index);
         }
-    }
 
-    fn jump_to_next_comment(&mut self) {
-        let Some(current_index) = current_index_from_scroll(
-            &self.comment_start_lines,
-            self.comments.len(),
-            self.comments_scroll,
-        ) else {
+        if !self.has_reached_load_more_threshold() {
             return;
-        };
-
-        if let Some(next_index) = next_comment_index(self.comments.len(), current_index) {
-            self.jump_to_comment(next_index);
         }
+
+        self.request_more_posts();
     }
 
-    fn jump_to_previous_comment(&mut self) {
-        let Some(current_index) = current_index_from_scroll(
-            &self.comment_start_lines,
-            self.comments.len(),
-            self.comments_scroll,
-        ) else {
-            return;
-        };
+    fn posts_from_items(items: Vec<Item>, mutes: &MuteFilter) -> Vec<Post> {
+        items
+            .into_iter()
+            .filter_map(|item| {
+                if item.dead || item.deleted {
+                    return None;
+                }
 
-        if let Some(prev_index) = previous_comment_index(current_index) {
-            self.jump_to_comment(prev_index);
-        }
+                let post_type = PostType::from_kind(item.kind.as_deref())?;
+                let title = decode_html_entities(&item.title?);
+                let url = item.url?;
+                let author = item
+                    .by
+                    .filter(|author| !author.is_empty())
+                    .unwrap_or_else(|| "unknown".to_string());
+                if mutes.is_post_muted(&author, &title, &url) {
+                    return None;
+                }
+                let domain = if url == discussion_url(item.id) {
+                    SELF_POST_DOMAIN.to_string()
+                } else {
+                    url_domain(&url).unwrap_or_default()
+                };
+
+                let body = item.text.as_deref().map(parse_hn_html).unwrap_or_default();
+
+                Some(Post {
+                    id: item.id,
+                    title,
+                    url,
+                    domain,
+                    body,
+                    post_type,
+                    points: item.score.unwrap_or_default(),
+                    comments: item.descendants.unwrap_or_default(),
+                    author,
+                    published_at: item.time.unwrap_or_default(),
+                })
+            })
+            .collect()
     }
 
-    fn jump_to_comment(&mut self, index: usize) {
-        let Some(line) = self.comment_start_lines.get(index) else {
-            return;
-        };
+    /// Rebuilds the visible list from `feed_posts`, keeping the selected post
+    /// selected when it is still shown.
+    fn apply_posts_view(&mut self) {
+        let selected_id = self.selected_post().map(|post| post.id);
+        let selected_index = self.list_state.selected();
+        let domain_filter = self.domain_filter.as_deref();
+        let view = self.posts_view();
+        let now = Self::unix_now();
+        self.posts = self
+            .feed_posts
+            .iter()
+            .filter(|post| domain_filter.is_none_or(|domain| post.domain == domain))
+            .filter(|post| view.matches(&post.stats(), now))
+            .cloned()
+            .collect();
+        view.sort(&mut self.posts, Post::stats, now);
+
+        let selected = selected_id
+            .and_then(|id| self.posts.iter().position(|post| post.id == id))
+            .or_else(|| {
+                let last = self.posts.len().checked_sub(1)?;
+                Some(selected_index.unwrap_or(0).min(last))
+            });
+        self.list_state.select(selected);
+    }
 
-        self.comments_scroll = (*line).min(self.max_comment_scroll());
+    /// Sort and filters of the current feed.
+    fn posts_view(&self) -> PostsView {
+        self.saved_state
+            .posts_views
+            .get(self.selected_feed.label())
+            .cloned()
+            .unwrap_or_default()
     }
 
-    fn bookmarks_visible(&self) -> bool {
-        !self.bookmarks.is_empty()
+    fn update_posts_view(&mut self, update: impl FnOnce(&mut PostsView)) {
+        let mut view = self.posts_view();
+        update(&mut view);
+        self.status_message = Some(if view.is_default() {
+            "Showing posts in feed order".to_string()
+        } else {
+            format!("Showing posts {}", view.summary())
+        });
+
+        let key = self.selected_feed.label().to_string();
+        if view.is_default() {
+            self.saved_state.posts_views.remove(&key);
+        } else {
+            self.saved_state.posts_views.insert(key, view);
+        }
+        self.persist_state();
+        self.apply_posts_view();
     }
 
-    fn bookmarks_width_percent(&self) -> u16 {
-        if self.bookmarks_collapsed {
-            if self.comments_open { 10 } else { 12 }
-        } else if self.comments_open {
-            20
+    fn toggle_domain_filter(&mut self) {
+        if let Some(domain) = self.domain_filter.take() {
+            self.status_message =
+                Some(format!("Showing posts from all domains, not just {domain}"));
         } else {
-            30
+            let Some(domain) = self.selected_post().map(|post| post.domain.clone()) else {
+                return;
+            };
+            if domain.is_empty() {
+                self.status_message = Some("This post has no domain".to_string());
+                return;
+            }
+            self.status_message = Some(format!("Showing posts from {domain}"));
+            self.domain_filter = Some(domain);
         }
+        self.apply_posts_view();
     }
 
-    fn close_bookmarks_pane(&mut self) {
-        if self.bookmarks.is_empty() {
-            self.set_focus_pane(Pane::Posts);
-            return;
-        }
-        self.bookmarks_collapsed = true;
-        self.set_focus_pane(Pane::Posts);
+    fn cache_current_feed(&mut self) {
+        self.feed_cache.insert(
+            self.selected_feed,
+            CachedFeed {
+                story_ids: self.story_ids.clone(),
+                next_story_index: self.next_story_index,
+                has_more_posts: self.has_more_posts,
+                posts: self.feed_posts.clone(),
+                failed_items: self.failed_items.clone(),
+                selected_index: self.list_state.selected(),
+                last_fetched: self.last_fetched.clone(),
+            },
+        );
     }
 
-    fn open_bookmarks_pane(&mut self) {
-        if self.bookmarks.is_empty() {
-            return;
-        }
-        self.bookmarks_collapsed = false;
-        self.set_focus_pane(Pane::Bookmarks);
-        self.ensure_bookmarks_selection();
+    fn restore_feed_from_cache(&mut self, feed: FeedTab) -> bool {
+        let Some(cached) = self.feed_cache.get(&feed).cloned() else {
+            return false;
+        };
+
+        self.story_ids = cached.story_ids;
+        self.next_story_index = cached.next_story_index;
+        self.has_more_posts = cached.has_more_posts;
+        self.feed_posts = cached.posts;
+        self.failed_items = cached.failed_items;
+        self.last_fetched = cached.last_fetched;
+        self.posts_notice = None;
+        self.list_state.select(cached.selected_index);
+        self.apply_posts_view();
+
+        true
     }
 
-    fn focus_next_pane(&mut self) {
-        self.cycle_focus(1);
+    fn clear_feed_state(&mut self) {
+        self.story_ids.clear();
+        self.next_story_index = 0;
+        self.has_more_posts = true;
+        self.feed_posts.clear();
+        self.failed_items.clear();
+        self.posts.clear();
+        self.posts_notice = None;
+        self.last_fetched = None;
+        self.list_state.select(None);
     }
 
-    fn focus_previous_pane(&mut self) {
-        self.cycle_focus(-1);
+    fn select_next_feed(&mut self) {
+        self.switch_feed(1);
     }
 
-    fn cycle_focus(&mut self, delta: isize) {
-        let panes = self.visible_panes();
-        if panes.len() <= 1 {
-            return;
-        }
+    fn select_previous_feed(&mut self) {
+        self.switch_feed(-1);
+    }
 
-        let current_index = panes
-            .iter()
-            .position(|pane| *pane == self.focus_pane)
-            .unwrap_or(0) as isize;
-        let pane_count = panes.len() as isize;
-        let next_index = (current_index + delta + pane_count) % pane_count;
-        self.set_focus_pane(panes[next_index as usize]);
+    fn switch_feed(&mut self, delta: isize) {
+        let count = FeedTab::ALL.len() as isize;
+        let current = self.selected_feed.index() as isize;
+        let next_index = (current + delta + count) % count;
+        let next_feed = FeedTab::from_index(next_index as usize);
+        self.switch_to_feed(next_feed);
     }
 
-    fn visible_panes(&self) -> Vec<Pane> {
-        let mut panes = Vec::with_capacity(4);
-        panes.push(Pane::Feeds);
-        if self.bookmarks_visible() {
-            panes.push(Pane::Bookmarks);
+    fn switch_to_feed(&mut self, next_feed: FeedTab) {
+        if next_feed == self.selected_feed {
+            return;
         }
-        panes.push(Pane::Posts);
-        if self.comments_open {
-            panes.push(Pane::Comments);
+
+        self.cache_current_feed();
+        self.selected_feed = next_feed;
+        self.domain_filter = None;
+        if !self.restore_feed_from_cache(next_feed) {
+            self.clear_feed_state();
         }
-        panes
+        self.events.send(AppEvent::Refresh);
     }
 
-    fn ensure_focus_valid(&mut self) {
-        let panes = self.visible_panes();
-        if panes.is_empty() {
-            self.focus_pane = Pane::Posts;
+    fn select_next(&mut self) {
+        let len = self.posts.len();
+        if len == 0 {
+            self.list_state.select(None);
             return;
         }
 
-        if !panes.contains(&self.focus_pane) {
-            self.focus_pane = Pane::Posts;
-        }
+        let next = match self.list_state.selected() {
+            Some(i) if i + 1 < len => i + 1,
+            _ => 0,
+        };
 
-        self.ensure_bookmarks_selection();
+        self.list_state.select(Some(next));
     }
 
-    fn ensure_bookmarks_selection(&mut self) {
-        if self.bookmarks.is_empty() {
-            self.bookmarks_state.select(None);
-            self.bookmarks_collapsed = false;
-            if self.focus_pane == Pane::Bookmarks {
-                self.focus_pane = Pane::Posts;
-            }
+    fn select_previous(&mut self) {
+        let len = self.posts.len();
+        if len == 0 {
+            self.list_state.select(None);
             return;
         }
 
-        let max_index = self.bookmarks.len().saturating_sub(1);
-        let selected = self.bookmarks_state.selected().unwrap_or(0).min(max_index);
-        self.bookmarks_state.select(Some(selected));
+        let prev = match self.list_state.selected() {
+            Some(0) | None => len - 1,
+            Some(i) => i - 1,
+        };
+
+        self.list_state.select(Some(prev));
     }
 
-    fn select_next_bookmark(&mut self) {
-        if self.bookmarks.is_empty() {
-            self.bookmarks_state.select(None);
+    fn selected_post(&self) -> Option<&Post> {
+        let index = self.list_state.selected()?;
+        self.posts.get(index)
+    }
+
+    fn comments_post(&self) -> Option<&Post> {
+        let post_id = self.comments_for_post_id?;
+        self.viewed_post.as_ref().filter(|post| post.id == post_id)
+    }
+
+    fn bookmark_post(&mut self, post: Post) {
+        if self.bookmarks.iter().any(|bookmark| bookmark.id == post.id) {
             return;
         }
 
-        let len = self.bookmarks.len();
-        let next = match self.bookmarks_state.selected() {
-            Some(i) if i + 1 < len => i + 1,
-            _ => 0,
-        };
-        self.bookmarks_state.select(Some(next));
+        let was_empty = self.bookmarks.is_empty();
+        self.bookmarks.push(post);
+        if was_empty {
+            self.bookmarks_collapsed = true;
+        }
+        self.ensure_bookmarks_selection();
     }
 
-    fn select_previous_bookmark(&mut self) {
-        if self.bookmarks.is_empty() {
-            self.bookmarks_state.select(None);
+    fn bookmark_selected_post(&mut self) {
+        let Some(post) = self.selected_post().cloned() else {
             return;
-        }
+        };
+        self.bookmark_post(post);
+    }
 
-        let len = self.bookmarks.len();
-        let prev = match self.bookmarks_state.selected() {
-            Some(0) | None => len - 1,
-            Some(i) => i - 1,
+    fn bookmark_comments_post(&mut self) {
+        let Some(post) = self.comments_post().cloned() else {
+            return;
         };
-        self.bookmarks_state.select(Some(prev));
+        self.bookmark_post(post);
     }
 
-    fn remove_selected_bookmark(&mut self) {
-        let Some(selected) = self.bookmarks_state.selected() else {
+    fn selected_bookmark(&self) -> Option<&Post> {
+        let index = self.bookmarks_state.selected()?;
+        self.bookmarks.get(index)
+    }
+
+    fn open_selected_bookmark(&mut self) {
+        let Some(bookmark) = self.selected_bookmark() else {
             return;
         };
+        self.events.send(AppEvent::OpenPost(bookmark.url.clone()));
+    }
 
-        if selected >= self.bookmarks.len() {
-            self.ensure_bookmarks_selection();
+    fn open_all_bookmarks(&mut self) {
+        if self.bookmarks.is_empty() {
             return;
         }
 
-        self.bookmarks.remove(selected);
+        for bookmark in &self.bookmarks {
+            self.events.send(AppEvent::OpenPost(bookmark.url.clone()));
+        }
+    }
 
-        if self.bookmarks.is_empty() {
-            self.bookmarks_state.select(None);
-            self.bookmarks_collapsed = false;
-            if self.focus_pane == Pane::Bookmarks {
-                self.focus_pane = Pane::Posts;
-            }
+    fn select_post_from_bookmark(&mut self) {
+        let Some(bookmark) = self.selected_bookmark().cloned() else {
             return;
-        }
+        };
 
-        let next_selected = selected.min(self.bookmarks.len().saturating_sub(1));
-        self.bookmarks_state.select(Some(next_selected));
+        if let Some(post_index) = self.posts.iter().position(|post| post.id == bookmark.id) {
+            self.list_state.select(Some(post_index));
+        }
+        self.open_comments_for_post(bookmark);
     }
 
-    fn set_focus_pane(&mut self, pane: Pane) {
-        if self.focus_pane == Pane::Bookmarks && pane != Pane::Bookmarks && self.bookmarks_visible()
-        {
-            self.bookmarks_collapsed = true;
+    fn open_item_by_id(&mut self, item_id: u64) {
+        if let Some(post_index) = self.posts.iter().position(|post| post.id == item_id) {
+            self.list_state.select(Some(post_index));
+            self.open_comments_for_selected();
+            return;
         }
-        self.focus_pane = pane;
-        if pane == Pane::Bookmarks && self.bookmarks_visible() {
-            self.bookmarks_collapsed = false;
-            self.ensure_bookmarks_selection();
+
+        if let Some(bookmark) = self.bookmarks.iter().find(|post| post.id == item_id) {
+            self.open_comments_for_post(bookmark.clone());
+            return;
         }
+
+        self.reset_comments_state();
+        self.set_focus_pane(Pane::Comments);
+        self.comments_open = true;
+        self.comments_for_post_id = Some(item_id);
+        self.comments_loading = true;
+
+        let client = self.hn_client.clone();
+        self.events.send_async(async move {
+            let result = client.fetch_single_item(item_id).await.map(Box::new);
+            AppEvent::ItemFetched { item_id, result }
+        });
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::time::{Duration, Instant};
+    fn open_url(&mut self, url: String) {
+        let browser = env::var("BROWSER").ok();
+        let template = opener::resolve_template(self.config.opener.as_deref(), browser.as_deref());
+        self.events.send_async(async move {
+            let result = opener::open(url.clone(), template).await;
+            AppEvent::OpenFinished { url, result }
+        });
+    }
 
-    fn base_item(id: u64) -> Item {
-        Item {
-            id,
-            title: None,
-            url: None,
-            score: None,
-            descendants: None,
-            by: None,
-            time: None,
-            text: None,
-            kids: None,
-            kind: None,
-            dead: false,
-            deleted: false,
+    fn open_profile(&mut self, username: &str) {
+        if username == "unknown" {
+            self.status_message = Some("Author is unknown".to_string());
+            return;
         }
+
+        self.profile = Some(ProfileView::loading(username));
+        let client = self.hn_client.clone();
+        let username = username.to_string();
+        self.events.send_async(async move {
+            let result = client
+                .fetch_user_profile(&username, PROFILE_SUBMISSION_LIMIT)
+                .await
+                .map(|profile| profile.map(Box::new));
+            AppEvent::UserProfileFetched { username, result }
+        });
     }
 
-    #[test]
-    fn posts_from_items_filters_invalid_and_maps_defaults() {
-        let mut story = base_item(1);
-        story.title = Some("Story title".to_string());
-        story.url = Some("https://example.com/story".to_string());
+    fn open_reader_for_post(&mut self, post: &Post) {
+        if post.url == discussion_url(post.id) {
+            self.status_message = Some("This post has no linked article".to_string());
+            return;
+        }
+
+        let already_open = self
+            .reader
+            .as_ref()
+            .is_some_and(|reader| reader.url == post.url && reader.error.is_none());
+        if !already_open {
+            let article = self.cached_article(&post.url);
+            let fetch = article.is_none();
+            self.reader = Some(ReaderView {
+                url: post.url.clone(),
+                title: post.title.clone(),
+                article,
+                error: None,
+                scroll: 0,
+                line_count: 0,
+                viewport_height: 0,
+            });
+
+            if fetch {
+                let client = self.reader_client.clone();
+                let reader_command = self.config.reader_command.clone();
+                let url = post.url.clone();
+                self.events.send_async(async move {
+                    let result = match reader_command {
+                        Some(template) => {
+                            opener::dump(url.clone(), template)
+                                .await
+                                .map(|text| Article {
+                                    title: None,
+                                    blocks: vec![ArticleBlock::Preformatted(text)],
+                                })
+                        }
+                        None => client.fetch_article(&url).await,
+                    };
+                    AppEvent::ArticleFetched {
+                        url,
+                        result: result.map(Box::new),
+                    }
+                });
+            }
+        }
+
+        self.set_focus_pane(Pane::Reader);
+    }
+
+    fn close_reader(&mut self) {
+        self.reader = None;
+        if self.focus_pane == Pane::Reader {
+            let pane = if self.comments_open {
+                Pane::Comments
+            } else {
+                Pane::Posts
+            };
+            self.set_focus_pane(pane);
+        }
+    }
+
+    fn open_selected_post(&mut self) {
+        let Some(url) = self.selected_post().map(|post| post.url.clone()) else {
+            return;
+        };
+        self.events.send(AppEvent::OpenPost(url));
+    }
+
+    fn open_comments_post(&mut self) {
+        let Some(url) = self.comments_post().map(|post| post.url.clone()) else {
+            return;
+        };
+        self.events.send(AppEvent::OpenPost(url));
+    }
+
+    fn open_comments_for_selected(&mut self) {
+        let Some(post) = self.selected_post().cloned() else {
+            return;
+        };
+        self.open_comments_for_post(post);
+    }
+
+    fn open_comments_for_post(&mut self, post: Post) {
+        let post_id = post.id;
+        let post_type = post.post_type;
+
+        self.viewed_post = Some(post);
+        self.set_focus_pane(Pane::Comments);
+        self.comments_open = true;
+        self.comments_scroll = 0;
+        self.comments_viewport_height = 0;
+        self.comment_line_count = 0;
+        self.comment_start_lines.clear();
+        self.load_comments(post_id, post_type);
+    }
+
+    fn close_comments_view(&mut self) {
+        self.set_focus_pane(Pane::Posts);
+        self.reset_comments_state();
+    }
+
+    fn reset_comments_state(&mut self) {
+        self.comments_open = false;
+        self.viewed_post = None;
+        self.comments.clear();
+        self.comments_for_post_id = None;
+        self.comments_loading = false;
+        self.comments_loading_more = false;
+        self.comments_thread = None;
+        self.comments_error = None;
+        self.comments_notice = None;
+        self.link_picker = None;
+        self.comments_scroll = 0;
+        self.comments_viewport_height = 0;
+        self.comment_line_count = 0;
+        self.comment_start_lines.clear();
+        self.comment_cursor = None;
+    }
+
+    fn load_comments(&mut self, post_id: u64, post_type: PostType) {
+        self.comments_for_post_id = Some(post_id);
+        self.comments_error = None;
+        self.comments_notice = None;
+        self.comments_loading = false;
+        self.comments_loading_more = false;
+        self.comments_thread = None;
+        self.comment_start_lines.clear();
+
+        if post_type == PostType::Job {
+            self.comment_cursor = self.initial_comment_cursor();
+            self.comments.clear();
+            self.comments_notice = Some("Jobs do not have comment threads.".to_string());
+            return;
+        }
+
+        let should_refresh = if let Some(cached) = self.comments_cache.get(&post_id) {
+            self.comments = match cached.thread.as_ref() {
+                Some(thread) => self.arrange_comments(cached.comments.clone(), thread),
+                None => cached.comments.clone(),
+            };
+            self.comments_thread = cached.thread.clone();
+            cached.fetched_at.elapsed() >= Duration::from_secs(COMMENTS_CACHE_REFRESH_AFTER_SECS)
+        } else {
+            self.comments.clear();
+            true
+        };
+        self.comment_cursor = self.initial_comment_cursor();
+
+        if !should_refresh {
+            return;
+        }
+
+        self.comments_loading = true;
+
+        let client = self.hn_client.clone();
+        let mutes = self.mute_filter.clone();
+        let progress = self.events.app_sender();
+        self.events.send_async(async move {
+            let result = client
+                .fetch_comments(post_id, COMMENTS_PAGE_SIZE, &mutes, |comments| {
+                    progress.send(AppEvent::CommentsProgress { post_id, comments })
+                })
+                .await
+                .map(Box::new);
+            AppEvent::LoadCommentsComplete { post_id, result }
+        });
+    }
+
+    fn load_more_comments_if_near_end(&mut self) {
+        if self.comments_loading || self.comments_loading_more {
+            return;
+        }
+
+        let Some(post_id) = self.comments_for_post_id else {
+            return;
+        };
+        let Some(thread) = self
+            .comments_thread
+            .as_ref()
+            .filter(|thread| thread.has_more())
+            .cloned()
+        else {
+            return;
+        };
+
+        let visible_end = self.comments_scroll as usize + self.comments_viewport_height;
+        if visible_end + self.comments_viewport_height < self.comment_line_count {
+            return;
+        }
+
+        let limit = self.comments.len() + COMMENTS_PAGE_SIZE;
+        self.request_more_comments(post_id, thread, limit);
+    }
+
+    fn request_more_comments(&mut self, post_id: u64, thread: CommentThread, limit: usize) {
+        self.comments_loading_more = true;
+        let client = self.hn_client.clone();
+        let mutes = self.mute_filter.clone();
+        let progress = self.events.app_sender();
+        self.events.send_async(async move {
+            let page = client
+                .fetch_more_comments(thread, limit, &mutes, |comments| {
+                    progress.send(AppEvent::CommentsProgress { post_id, comments })
+                })
+                .await;
+            let page = Box::new(page);
+            AppEvent::MoreCommentsLoaded { post_id, page }
+        });
+    }
+
+    fn max_comment_scroll(&self) -> u16 {
+        self.comment_line_count
+            .saturating_sub(self.comments_viewport_height) as u16
+    }
+
+    fn comment_page_step(&self) -> u16 {
+        self.comments_viewport_height.saturating_sub(1).max(1) as u16
+    }
+
+    fn clamp_comments_scroll(&mut self) {
+        let max_scroll = self.max_comment_scroll();
+        if self.comments_scroll > max_scroll {
+            self.comments_scroll = max_scroll;
+        }
+    }
+
+    fn scroll_comments_up(&mut self, amount: u16) {
+        self.comments_scroll = self.comments_scroll.saturating_sub(amount);
+    }
+
+    fn scroll_comments_down(&mut self, amount: u16) {
+        let max_scroll = self.max_comment_scroll();
+        self.comments_scroll = self.comments_scroll.saturating_add(amount).min(max_scroll);
+    }
+
+    /// Where the cursor starts in a thread: on the first comment, unless a post
+    /// body or poll sits above the comments.
+    fn initial_comment_cursor(&self) -> Option<usize> {
+        let has_post_body = self
+            .comments_post()
+            .is_some_and(|post| !post.body.is_empty());
+        let has_poll = self
+            .comments_thread
+            .as_ref()
+            .is_some_and(|thread| !thread.poll_options().is_empty());
+        (!has_post_body && !has_poll).then_some(0)
+    }
+
+    fn focused_comment_index(&self) -> Option<usize> {
+        self.comment_cursor
+            .filter(|&index| index < self.comments.len())
+    }
+
+    fn focused_comment(&self) -> Option<&Comment> {
+        self.focused_comment_index()
+            .and_then(|index| self.comments.get(index))
+    }
+
+    fn copy_to_clipboard(&mut self, what: &str, text: String) {
+        let command = self
+            .config
+            .clipboard_command
+            .as_deref()
+            .map(str::trim)
+            .filter(|command| !command.is_empty());
+        let Some(command) = command else {
+            let result = clipboard::copy_osc52(&text);
+            self.handle_copy_finished(what, result);
+            return;
+        };
+        // The command is only a fallback for when OSC 52 is unavailable.
+        if self.osc52_supported && clipboard::copy_osc52(&text).is_ok() {
+            self.handle_copy_finished(what, Ok(()));
+            return;
+        }
+
+        let command = command.to_string();
+        let what = what.to_string();
+        self.events.send_async(async move {
+            let result = clipboard::copy_with_command(command, text).await;
+            AppEvent::CopyFinished { what, result }
+        });
+    }
+
+    fn handle_copy_finished(&mut self, what: &str, result: Result<(), String>) {
+        self.status_message = Some(match result {
+            Ok(()) => format!("Copied {what}"),
+            Err(error) => format!("Failed to copy {what}: {error}"),
+        });
+    }
+
+    fn open_link_picker(&mut self, scope: LinkScope) {
+        let picker = LinkPicker::new(&self.comments, self.focused_comment_index(), scope);
+        if picker.is_empty() {
+            self.status_message = Some(match scope {
+                LinkScope::Comment => "No links in this comment".to_string(),
+                LinkScope::Thread => "No links in this thread".to_string(),
+            });
+            return;
+        }
+
+        self.link_picker = Some(picker);
+    }
+
+    fn jump_to_next_sibling_comment(&mut self) {
+        let next_index = match self.focused_comment_index() {
+            Some(current_index) => next_sibling_or_outer_index(&self.comments, current_index),
+            None => Some(0),
+        };
+        if let Some(next_index) = next_index {
+            self.jump_to_comment(next_index);
+        }
+    }
+
+    fn jump_to_previous_sibling_comment(&mut self) {
+        self.jump_from_focused_comment(previous_sibling_or_parent_index);
+    }
+
+    fn jump_to_next_comment(&mut self) {
+        let next_index = match self.focused_comment_index() {
+            Some(current_index) => next_comment_index(self.comments.len(), current_index),
+            None => Some(0),
+        };
+        if let Some(next_index) = next_index {
+            self.jump_to_comment(next_index);
+        }
+    }
+
+    fn jump_to_previous_comment(&mut self) {
+        if let Some(prev_index) = self
+            .focused_comment_index()
+            .and_then(previous_comment_index)
+        {
+            self.jump_to_comment(prev_index);
+        }
+    }
+
+    fn jump_to_next_op_comment(&mut self) {
+        let Some(author) = self.comments_post().map(|post| post.author.clone()) else {
+            return;
+        };
+        match next_author_index(&self.comments, self.focused_comment_index(), &author) {
+            Some(index) => self.jump_to_comment(index),
+            None => self.status_message = Some("No more comments from OP".to_string()),
+        }
+    }
+
+    fn jump_from_focused_comment(&mut self, target: fn(&[Comment], usize) -> Option<usize>) {
+        if let Some(index) = self
+            .focused_comment_index()
+            .and_then(|current_index| target(&self.comments, current_index))
+        {
+            self.jump_to_comment(index);
+        }
+    }
+
+    /// Authors of the focused comment's ancestors, e.g. `alice › bob`.
+    fn focused_comment_breadcrumbs(&self) -> Option<String> {
+        let index = self.focused_comment_index()?;
+        let ancestors = ancestor_indices(&self.comments, index);
+        if ancestors.is_empty() {
+            return None;
+        }
+
+        Some(
+            ancestors
+                .iter()
+                .map(|&index| {
+                    let comment = &self.comments[index];
+                    if comment.muted && !self.revealed_comment_ids.contains(&comment.id) {
+                        "[muted]"
+                    } else {
+                        comment.author.as_str()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" › "),
+        )
+    }
+
+    /// Focuses the comment and scrolls just enough to bring it into view,
+    /// aligning it to the top when it is taller than the viewport.
+    fn jump_to_comment(&mut self, index: usize) {
+        if index >= self.comments.len() {
+            return;
+        }
+        self.comment_cursor = Some(index);
+        self.scroll_to_focused_comment();
+    }
+
+    fn scroll_to_focused_comment(&mut self) {
+        let Some(index) = self.focused_comment_index() else {
+            return;
+        };
+        let Some(&start) = self.comment_start_lines.get(index) else {
+            return;
+        };
+        let end = self
+            .comment_start_lines
+            .get(index + 1)
+            .map_or(self.comment_line_count, |&next| next as usize);
+        let bottom_aligned = end.saturating_sub(self.comments_viewport_height) as u16;
+
+        self.comments_scroll = self
+            .comments_scroll
+            .max(bottom_aligned)
+            .min(start)
+            .min(self.max_comment_scroll());
+    }
+
+    fn bookmarks_visible(&self) -> bool {
+        !self.bookmarks.is_empty()
+    }
+
+    fn bookmarks_width_percent(&self) -> u16 {
+        if self.bookmarks_collapsed {
+            if self.comments_open { 10 } else { 12 }
+        } else if self.comments_open {
+            20
+        } else {
+            30
+        }
+    }
+
+    fn close_bookmarks_pane(&mut self) {
+        if self.bookmarks.is_empty() {
+            self.set_focus_pane(Pane::Posts);
+            return;
+        }
+        self.bookmarks_collapsed = true;
+        self.set_focus_pane(Pane::Posts);
+    }
+
+    fn open_bookmarks_pane(&mut self) {
+        if self.bookmarks.is_empty() {
+            return;
+        }
+        self.bookmarks_collapsed = false;
+        self.set_focus_pane(Pane::Bookmarks);
+        self.ensure_bookmarks_selection();
+    }
+
+    fn focus_next_pane(&mut self) {
+        self.cycle_focus(1);
+    }
+
+    fn focus_previous_pane(&mut self) {
+        self.cycle_focus(-1);
+    }
+
+    fn cycle_focus(&mut self, delta: isize) {
+        let panes = self.visible_panes();
+        if panes.len() <= 1 {
+            return;
+        }
+
+        let current_index = panes
+            .iter()
+            .position(|pane| *pane == self.focus_pane)
+            .unwrap_or(0) as isize;
+        let pane_count = panes.len() as isize;
+        let next_index = (current_index + delta + pane_count) % pane_count;
+        self.set_focus_pane(panes[next_index as usize]);
+    }
+
+    fn visible_panes(&self) -> Vec<Pane> {
+        let mut panes = Vec::with_capacity(4);
+        panes.push(Pane::Feeds);
+        if self.bookmarks_visible() {
+            panes.push(Pane::Bookmarks);
+        }
+        panes.push(Pane::Posts);
+        if self.comments_open {
+            panes.push(Pane::Comments);
+        }
+        if self.reader.is_some() {
+            panes.push(Pane::Reader);
+        }
+        if !self.alerts.is_empty() {
+            panes.push(Pane::Alerts);
+        }
+        panes
+    }
+
+    fn ensure_focus_valid(&mut self) {
+        let panes = self.visible_panes();
+        if panes.is_empty() {
+            self.focus_pane = Pane::Posts;
+            return;
+        }
+
+        if !panes.contains(&self.focus_pane) {
+            self.focus_pane = Pane::Posts;
+        }
+
+        self.ensure_bookmarks_selection();
+    }
+
+    fn ensure_bookmarks_selection(&mut self) {
+        if self.bookmarks.is_empty() {
+            self.bookmarks_state.select(None);
+            self.bookmarks_collapsed = false;
+            if self.focus_pane == Pane::Bookmarks {
+                self.focus_pane = Pane::Posts;
+            }
+            return;
+        }
+
+        let max_index = self.bookmarks.len().saturating_sub(1);
+        let selected = self.bookmarks_state.selected().unwrap_or(0).min(max_index);
+        self.bookmarks_state.select(Some(selected));
+    }
+
+    fn select_next_bookmark(&mut self) {
+        if self.bookmarks.is_empty() {
+            self.bookmarks_state.select(None);
+            return;
+        }
+
+        let len = self.bookmarks.len();
+        let next = match self.bookmarks_state.selected() {
+            Some(i) if i + 1 < len => i + 1,
+            _ => 0,
+        };
+        self.bookmarks_state.select(Some(next));
+    }
+
+    fn select_previous_bookmark(&mut self) {
+        if self.bookmarks.is_empty() {
+            self.bookmarks_state.select(None);
+            return;
+        }
+
+        let len = self.bookmarks.len();
+        let prev = match self.bookmarks_state.selected() {
+            Some(0) | None => len - 1,
+            Some(i) => i - 1,
+        };
+        self.bookmarks_state.select(Some(prev));
+    }
+
+    fn remove_selected_bookmark(&mut self) {
+        let Some(selected) = self.bookmarks_state.selected() else {
+            return;
+        };
+
+        if selected >= self.bookmarks.len() {
+            self.ensure_bookmarks_selection();
+            return;
+        }
+
+        self.bookmarks.remove(selected);
+
+        if self.bookmarks.is_empty() {
+            self.bookmarks_state.select(None);
+            self.bookmarks_collapsed = false;
+            if self.focus_pane == Pane::Bookmarks {
+                self.focus_pane = Pane::Posts;
+            }
+            return;
+        }
+
+        let next_selected = selected.min(self.bookmarks.len().saturating_sub(1));
+        self.bookmarks_state.select(Some(next_selected));
+    }
+
+    fn set_focus_pane(&mut self, pane: Pane) {
+        if self.focus_pane == Pane::Bookmarks && pane != Pane::Bookmarks && self.bookmarks_visible()
+        {
+            self.bookmarks_collapsed = true;
+        }
+        self.focus_pane = pane;
+        if pane == Pane::Bookmarks && self.bookmarks_visible() {
+            self.bookmarks_collapsed = false;
+            self.ensure_bookmarks_selection();
+        }
+    }
+}
+
+fn hn_client(config: &Config) -> HackerNewsApi {
+    let client = HackerNewsApi::new().with_retries(config.retries.unwrap_or(DEFAULT_RETRIES));
+    match config.api_url.as_deref() {
+        Some(api_url) => client.with_base_url(api_url.trim_end_matches('/')),
+        None => client,
+    }
+}
+
+fn load_config(path: Option<&Path>) -> (Config, Option<String>) {
+    let Some(path) = path else {
+        return (Config::default(), None);
+    };
+    match Config::load(path) {
+        Ok(config) => (config, None),
+        Err(error) => (Config::default(), Some(error)),
+    }
+}
+
+fn load_saved_state(path: Option<&Path>) -> (SavedState, Option<String>) {
+    let Some(path) = path else {
+        return (SavedState::default(), None);
+    };
+    match SavedState::load(path) {
+        Ok(state) => (state, None),
+        Err(error) => (SavedState::default(), Some(error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hn::{User, UserProfile},
+        html::{blocks_to_plain_text, parse_hn_html},
+        posts_view::PostSort,
+        watch::WatchRule,
+    };
+    use crossterm::event::KeyModifiers;
+    use reqwest::StatusCode;
+    use serde_json::{Value, json};
+    use std::time::{Duration, Instant};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    fn base_item(id: u64) -> Item {
+        Item {
+            id,
+            title: None,
+            url: None,
+            score: None,
+            descendants: None,
+            by: None,
+            time: None,
+            text: None,
+            kids: None,
+            parent: None,
+            parts: None,
+            kind: None,
+            dead: false,
+            deleted: false,
+        }
+    }
+
+    #[test]
+    fn posts_from_items_filters_invalid_and_maps_defaults() {
+        let mut story = base_item(1);
+        story.title = Some("Story title".to_string());
+        story.url = Some("https://example.com/story".to_string());
         story.kind = Some("story".to_string());
         story.score = Some(123);
         story.descendants = Some(45);
         story.by = Some("alice".to_string());
         story.time = Some(1_700_000_000);
 
-        let mut job = base_item(2);
-        job.title = Some("Job title".to_string());
-        job.url = Some("https://example.com/job".to_string());
-        job.kind = Some("job".to_string());
-        job.by = Some(String::new());
+        let mut job = base_item(2);
+        job.title = Some("Job title".to_string());
+        job.url = Some("https://example.com/job".to_string());
+        job.kind = Some("job".to_string());
+        job.by = Some(String::new());
+
+        let mut comment_kind = base_item(3);
+        comment_kind.title = Some("Comment-like post".to_string());
+        comment_kind.url = Some("https://example.com/comment".to_string());
+        comment_kind.kind = Some("comment".to_string());
+
+        let mut dead_story = base_item(4);
+        dead_story.title = Some("Dead".to_string());
+        dead_story.url = Some("https://example.com/dead".to_string());
+        dead_story.kind = Some("story".to_string());
+        dead_story.dead = true;
+
+        let mut missing_title = base_item(5);
+        missing_title.url = Some("https://example.com/missing-title".to_string());
+        missing_title.kind = Some("story".to_string());
+
+        let posts = App::posts_from_items(
+            vec![story, job, comment_kind, dead_story, missing_title],
+            &MuteFilter::default(),
+        );
+
+        assert_eq!(posts.len(), 2);
+
+        assert_eq!(posts[0].id, 1);
+        assert_eq!(posts[0].title, "Story title");
+        assert_eq!(posts[0].url, "https://example.com/story");
+        assert!(matches!(posts[0].post_type, PostType::Story));
+        assert_eq!(posts[0].points, 123);
+        assert_eq!(posts[0].comments, 45);
+        assert_eq!(posts[0].author, "alice");
+        assert_eq!(posts[0].published_at, 1_700_000_000);
+
+        assert_eq!(posts[1].id, 2);
+        assert_eq!(posts[1].title, "Job title");
+        assert_eq!(posts[1].url, "https://example.com/job");
+        assert!(matches!(posts[1].post_type, PostType::Job));
+        assert_eq!(posts[1].points, 0);
+        assert_eq!(posts[1].comments, 0);
+        assert_eq!(posts[1].author, "unknown");
+        assert_eq!(posts[1].published_at, 0);
+    }
+
+    #[tokio::test]
+    async fn posts_from_items_decodes_entities_in_titles() {
+        let mut story = base_item(1);
+        story.title = Some("Q&amp;A &#8211; &hellip; &#x2014; &amp;lt;".to_string());
+        story.url = Some("https://example.com/story".to_string());
+        story.kind = Some("story".to_string());
+
+        let posts = App::posts_from_items(vec![story], &MuteFilter::default());
+
+        assert_eq!(posts[0].title, "Q&A \u{2013} \u{2026} \u{2014} &lt;");
+    }
+
+    #[tokio::test]
+    async fn posts_from_items_keeps_polls() {
+        let mut poll = base_item(1);
+        poll.title = Some("Poll: Tabs or spaces?".to_string());
+        poll.url = Some("https://news.ycombinator.com/item?id=1".to_string());
+        poll.kind = Some("poll".to_string());
+        poll.parts = Some(vec![2, 3]);
+
+        let posts = App::posts_from_items(vec![poll], &MuteFilter::default());
+
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].post_type, PostType::Poll);
+    }
+
+    fn sample_post(id: u64, title: &str) -> Post {
+        Post {
+            id,
+            title: title.to_string(),
+            url: format!("https://example.com/{id}"),
+            domain: "example.com".to_string(),
+            body: Vec::new(),
+            post_type: PostType::Story,
+            points: 0,
+            comments: 0,
+            author: "author".to_string(),
+            published_at: 0,
+        }
+    }
+
+    fn sample_comment(author: &str, text: &str) -> Comment {
+        Comment {
+            id: 0,
+            author: author.to_string(),
+            body: parse_hn_html(text),
+            published_at: 0,
+            depth: 0,
+            ancestor_has_next_sibling: Vec::new(),
+            is_last_sibling: true,
+            muted: false,
+        }
+    }
 
-        let mut comment_kind = base_item(3);
-        comment_kind.title = Some("Comment-like post".to_string());
-        comment_kind.url = Some("https://example.com/comment".to_string());
-        comment_kind.kind = Some("comment".to_string());
+    /// Serves `items` as the HN API on a local port; other item IDs get a 404.
+    async fn api_fixture(items: Vec<Value>) -> HackerNewsApi {
+        let items: HashMap<u64, String> = items
+            .into_iter()
+            .map(|item| (item["id"].as_u64().unwrap(), item.to_string()))
+            .collect();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 1024];
+                let read = socket.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..read]);
+                let body = request
+                    .split_whitespace()
+                    .nth(1)
+                    .and_then(|path| path.strip_prefix("/item/")?.strip_suffix(".json"))
+                    .and_then(|id| items.get(&id.parse().ok()?));
+                let response = match body {
+                    Some(body) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    ),
+                    None => {
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    }
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        HackerNewsApi::new().with_base_url(format!("http://{address}"))
+    }
 
-        let mut dead_story = base_item(4);
-        dead_story.title = Some("Dead".to_string());
-        dead_story.url = Some("https://example.com/dead".to_string());
-        dead_story.kind = Some("story".to_string());
-        dead_story.dead = true;
+    /// Handles the app's events until one matching `done` has been handled.
+    async fn handle_events_until(app: &mut App, done: impl Fn(&AppEvent) -> bool) {
+        let handle_events = async {
+            loop {
+                if let Event::App(event) = app.events.next().await.unwrap() {
+                    let finished = done(&event);
+                    app.handle_app_event(event);
+                    if finished {
+                        return;
+                    }
+                }
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(10), handle_events)
+            .await
+            .unwrap();
+    }
 
-        let mut missing_title = base_item(5);
-        missing_title.url = Some("https://example.com/missing-title".to_string());
-        missing_title.kind = Some("story".to_string());
+    #[tokio::test]
+    async fn with_paths_loads_config_and_state_and_saves_back() {
+        let dir = env::temp_dir().join(format!("lazynews-app-paths-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+        let state_path = dir.join("state.toml");
+        std::fs::write(&config_path, "opener = \"lynx -dump\"").unwrap();
+        std::fs::write(&state_path, "followed_users = [\"alice\"]").unwrap();
+
+        let mut app = App::with_paths(Some(config_path), Some(state_path.clone()));
+        assert_eq!(app.config.opener.as_deref(), Some("lynx -dump"));
+        assert_eq!(app.saved_state.followed_users, vec!["alice".to_string()]);
+
+        app.saved_state.toggle_follow("bob");
+        app.persist_state();
+        let saved = SavedState::load(&state_path).unwrap();
+        assert_eq!(saved.followed_users, vec!["alice", "bob"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 
-        let posts =
-            App::posts_from_items(vec![story, job, comment_kind, dead_story, missing_title]);
+    #[tokio::test]
+    async fn bookmark_selected_post_adds_once_per_post_id() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first"), sample_post(2, "second")];
+        app.list_state.select(Some(0));
 
-        assert_eq!(posts.len(), 2);
+        app.bookmark_selected_post();
+        app.bookmark_selected_post();
+        app.list_state.select(Some(1));
+        app.bookmark_selected_post();
+
+        assert_eq!(app.bookmarks.len(), 2);
+        assert_eq!(app.bookmarks[0].id, 1);
+        assert_eq!(app.bookmarks[1].id, 2);
+    }
+
+    #[tokio::test]
+    async fn bookmarks_are_hidden_until_first_bookmark() {
+        let mut app = App::with_paths(None, None);
+        assert!(!app.bookmarks_visible());
+
+        app.posts = vec![sample_post(1, "first")];
+        app.list_state.select(Some(0));
+        app.bookmark_selected_post();
+
+        assert!(app.bookmarks_visible());
+        assert!(app.bookmarks_collapsed);
+    }
+
+    #[tokio::test]
+    async fn focus_cycles_between_comments_posts_bookmarks_and_feeds() {
+        let mut app = App::with_paths(None, None);
+        app.comments_open = true;
+        app.focus_pane = Pane::Comments;
+        app.posts = vec![sample_post(1, "first")];
+        app.list_state.select(Some(0));
+        app.bookmark_selected_post();
+
+        app.focus_previous_pane();
+        assert_eq!(app.focus_pane, Pane::Posts);
+
+        app.focus_previous_pane();
+        assert_eq!(app.focus_pane, Pane::Bookmarks);
+
+        app.focus_previous_pane();
+        assert_eq!(app.focus_pane, Pane::Feeds);
+    }
+
+    #[tokio::test]
+    async fn posts_pane_supports_vim_style_jk_navigation() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first"), sample_post(2, "second")];
+        app.list_state.select(Some(0));
+
+        app.handle_posts_key(KeyCode::Char('j'));
+        assert_eq!(app.list_state.selected(), Some(1));
+
+        app.handle_posts_key(KeyCode::Char('k'));
+        assert_eq!(app.list_state.selected(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn comments_pane_bookmarks_the_post_being_viewed() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first"), sample_post(2, "second")];
+        app.comments_open = true;
+        app.comments_for_post_id = Some(1);
+        app.viewed_post = Some(sample_post(1, "first"));
+        app.focus_pane = Pane::Comments;
+        app.list_state.select(Some(1));
+
+        app.handle_comments_key(KeyCode::Char('b'));
+
+        assert_eq!(app.bookmarks.len(), 1);
+        assert_eq!(app.bookmarks[0].id, 1);
+    }
+
+    #[tokio::test]
+    async fn opening_comments_uses_fresh_cache_without_fetch() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first")];
+        app.list_state.select(Some(0));
+        app.comments_cache.insert(
+            1,
+            CachedComments {
+                comments: vec![sample_comment("alice", "cached")],
+                thread: None,
+                fetched_at: Instant::now(),
+            },
+        );
+
+        app.open_comments_for_selected();
+
+        assert!(app.comments_open);
+        assert_eq!(app.comments_for_post_id, Some(1));
+        assert!(!app.comments_loading);
+        assert_eq!(app.comments.len(), 1);
+        assert_eq!(blocks_to_plain_text(&app.comments[0].body), "cached");
+    }
+
+    #[tokio::test]
+    async fn opening_comments_with_stale_cache_keeps_comments_and_refreshes() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first")];
+        app.list_state.select(Some(0));
+        app.comments_cache.insert(
+            1,
+            CachedComments {
+                comments: vec![sample_comment("alice", "cached")],
+                thread: None,
+                fetched_at: Instant::now()
+                    - Duration::from_secs(COMMENTS_CACHE_REFRESH_AFTER_SECS + 1),
+            },
+        );
+
+        app.open_comments_for_selected();
+
+        assert!(app.comments_open);
+        assert_eq!(app.comments_for_post_id, Some(1));
+        assert!(app.comments_loading);
+        assert_eq!(app.comments.len(), 1);
+        assert_eq!(blocks_to_plain_text(&app.comments[0].body), "cached");
+    }
+
+    #[tokio::test]
+    async fn failed_comments_refresh_keeps_cached_comments_visible() {
+        let mut app = App::with_paths(None, None);
+        app.comments_open = true;
+        app.comments_for_post_id = Some(1);
+        app.comments_loading = true;
+        app.comments = vec![sample_comment("alice", "cached")];
+        app.comments_cache.insert(
+            1,
+            CachedComments {
+                comments: vec![sample_comment("alice", "cached")],
+                thread: None,
+                fetched_at: Instant::now()
+                    - Duration::from_secs(COMMENTS_CACHE_REFRESH_AFTER_SECS + 1),
+            },
+        );
+
+        app.handle_app_event(AppEvent::LoadCommentsComplete {
+            post_id: 1,
+            result: Err(FetchError::Offline),
+        });
+
+        assert!(!app.comments_loading);
+        assert_eq!(app.comments.len(), 1);
+        assert_eq!(blocks_to_plain_text(&app.comments[0].body), "cached");
+        assert!(app.comments_error.is_none());
+    }
+
+    #[tokio::test]
+    async fn feed_switching_requires_feeds_focus() {
+        let mut app = App::with_paths(None, None);
+        assert_eq!(app.selected_feed, FeedTab::Top);
+
+        app.handle_posts_key(KeyCode::Right);
+        assert_eq!(app.selected_feed, FeedTab::Top);
+
+        app.handle_feeds_key(KeyCode::Right);
+        assert_eq!(app.selected_feed, FeedTab::New);
+
+        app.handle_feeds_key(KeyCode::Left);
+        assert_eq!(app.selected_feed, FeedTab::Top);
+    }
+
+    #[tokio::test]
+    async fn enter_in_feeds_pane_moves_focus_to_posts() {
+        let mut app = App::with_paths(None, None);
+        app.focus_pane = Pane::Feeds;
+
+        app.handle_feeds_key(KeyCode::Enter);
+
+        assert_eq!(app.focus_pane, Pane::Posts);
+    }
+
+    #[tokio::test]
+    async fn pane_shortcuts_focus_panes() {
+        let mut app = App::with_paths(None, None);
+        app.focus_pane = Pane::Feeds;
+        app.comments_open = true;
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE))
+            .expect("pane key should be handled");
+        assert_eq!(app.focus_pane, Pane::Posts);
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('4'), KeyModifiers::NONE))
+            .expect("pane key should be handled");
+        assert_eq!(app.focus_pane, Pane::Feeds);
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('3'), KeyModifiers::NONE))
+            .expect("pane key should be handled");
+        assert_eq!(app.focus_pane, Pane::Comments);
+    }
+
+    #[tokio::test]
+    async fn feed_switch_refresh_keeps_feeds_focus() {
+        let mut app = App::with_paths(None, None);
+        app.focus_pane = Pane::Feeds;
+
+        app.handle_feeds_key(KeyCode::Right);
+        app.handle_app_event(AppEvent::Refresh);
+
+        assert_eq!(app.selected_feed, FeedTab::New);
+        assert_eq!(app.focus_pane, Pane::Feeds);
+    }
+
+    #[tokio::test]
+    async fn key_one_toggles_bookmarks_when_focused() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first")];
+        app.list_state.select(Some(0));
+        app.bookmark_selected_post();
+        app.focus_pane = Pane::Bookmarks;
+        app.bookmarks_collapsed = false;
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('1'), KeyModifiers::NONE))
+            .expect("pane key should be handled");
+        assert!(app.bookmarks_collapsed);
+        assert_eq!(app.focus_pane, Pane::Bookmarks);
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('1'), KeyModifiers::NONE))
+            .expect("pane key should be handled");
+        assert!(!app.bookmarks_collapsed);
+        assert_eq!(app.focus_pane, Pane::Bookmarks);
+    }
+
+    #[tokio::test]
+    async fn navigating_away_from_bookmarks_collapses_it() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first")];
+        app.list_state.select(Some(0));
+        app.bookmark_selected_post();
+        app.open_bookmarks_pane();
+
+        assert_eq!(app.focus_pane, Pane::Bookmarks);
+        assert!(!app.bookmarks_collapsed);
 
-        assert_eq!(posts[0].id, 1);
-        assert_eq!(posts[0].title, "Story title");
-        assert_eq!(posts[0].url, "https://example.com/story");
-        assert!(matches!(posts[0].post_type, PostType::Story));
-        assert_eq!(posts[0].points, 123);
-        assert_eq!(posts[0].comments, 45);
-        assert_eq!(posts[0].author, "alice");
-        assert_eq!(posts[0].published_at, 1_700_000_000);
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE))
+            .expect("pane key should be handled");
 
-        assert_eq!(posts[1].id, 2);
-        assert_eq!(posts[1].title, "Job title");
-        assert_eq!(posts[1].url, "https://example.com/job");
-        assert!(matches!(posts[1].post_type, PostType::Job));
-        assert_eq!(posts[1].points, 0);
-        assert_eq!(posts[1].comments, 0);
-        assert_eq!(posts[1].author, "unknown");
-        assert_eq!(posts[1].published_at, 0);
+        assert_eq!(app.focus_pane, Pane::Posts);
+        assert!(app.bookmarks_collapsed);
     }
 
-    fn sample_post(id: u64, title: &str) -> Post {
-        Post {
-            id,
-            title: title.to_string(),
-            url: format!("https://example.com/{id}"),
-            post_type: PostType::Story,
-            points: 0,
-            comments: 0,
-            author: "author".to_string(),
-            published_at: 0,
-        }
-    }
+    #[tokio::test]
+    async fn tab_focus_to_bookmarks_expands_it() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first")];
+        app.list_state.select(Some(0));
+        app.bookmark_selected_post();
+        app.focus_pane = Pane::Posts;
+        app.bookmarks_collapsed = true;
 
-    fn sample_comment(author: &str, text: &str) -> Comment {
-        Comment {
-            author: author.to_string(),
-            text: text.to_string(),
-            published_at: 0,
-            depth: 0,
-            ancestor_has_next_sibling: Vec::new(),
-            is_last_sibling: true,
-        }
+        app.handle_key_event(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT))
+            .expect("pane key should be handled");
+
+        assert_eq!(app.focus_pane, Pane::Bookmarks);
+        assert!(!app.bookmarks_collapsed);
     }
 
     #[tokio::test]
-    async fn bookmark_selected_post_adds_once_per_post_id() {
-        let mut app = App::new();
+    async fn deleting_bookmarks_updates_focus_and_selection() {
+        let mut app = App::with_paths(None, None);
         app.posts = vec![sample_post(1, "first"), sample_post(2, "second")];
         app.list_state.select(Some(0));
-
-        app.bookmark_selected_post();
         app.bookmark_selected_post();
         app.list_state.select(Some(1));
         app.bookmark_selected_post();
+        app.focus_pane = Pane::Bookmarks;
+        app.bookmarks_collapsed = false;
+        app.bookmarks_state.select(Some(0));
 
-        assert_eq!(app.bookmarks.len(), 2);
-        assert_eq!(app.bookmarks[0].id, 1);
-        assert_eq!(app.bookmarks[1].id, 2);
-    }
+        app.handle_bookmarks_key(KeyCode::Char('d'));
+        assert_eq!(app.bookmarks.len(), 1);
+        assert_eq!(app.bookmarks[0].id, 2);
+        assert_eq!(app.bookmarks_state.selected(), Some(0));
+        assert_eq!(app.focus_pane, Pane::Bookmarks);
 
-    #[tokio::test]
-    async fn bookmarks_are_hidden_until_first_bookmark() {
-        let mut app = App::new();
+        app.handle_bookmarks_key(KeyCode::Char('d'));
+        assert!(app.bookmarks.is_empty());
         assert!(!app.bookmarks_visible());
+        assert_eq!(app.focus_pane, Pane::Posts);
+    }
 
-        app.posts = vec![sample_post(1, "first")];
+    #[tokio::test]
+    async fn opening_all_bookmarks_keeps_bookmark_state() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first"), sample_post(2, "second")];
         app.list_state.select(Some(0));
         app.bookmark_selected_post();
+        app.list_state.select(Some(1));
+        app.bookmark_selected_post();
+        app.focus_pane = Pane::Bookmarks;
+        app.bookmarks_collapsed = false;
+        app.bookmarks_state.select(Some(1));
 
-        assert!(app.bookmarks_visible());
-        assert!(app.bookmarks_collapsed);
+        app.handle_bookmarks_key(KeyCode::Char('a'));
+
+        assert_eq!(app.bookmarks.len(), 2);
+        assert_eq!(app.bookmarks_state.selected(), Some(1));
+        assert_eq!(app.focus_pane, Pane::Bookmarks);
+        assert!(!app.bookmarks_collapsed);
     }
 
     #[tokio::test]
-    async fn focus_cycles_between_comments_posts_bookmarks_and_feeds() {
-        let mut app = App::new();
+    async fn comment_navigation_starts_after_the_post_body() {
+        let mut app = App::with_paths(None, None);
         app.comments_open = true;
         app.focus_pane = Pane::Comments;
+        app.comments = vec![sample_comment("alice", "one"), sample_comment("bob", "two")];
+        app.comment_start_lines = vec![3, 5];
+        app.comment_line_count = 20;
+        assert_eq!(app.focused_comment_index(), None);
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Down)).unwrap();
+        assert_eq!(app.comments_scroll, 3);
+        assert_eq!(app.focused_comment_index(), Some(0));
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Down)).unwrap();
+        assert_eq!(app.focused_comment_index(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn comment_actions_target_the_first_comment_once_loaded() {
+        let mut app = App::with_paths(None, None);
+        app.hn_client = api_fixture(vec![
+            json!({"id": 1, "type": "story", "title": "first", "kids": [2]}),
+            json!({"id": 2, "type": "comment", "by": "alice", "text": "see https://a.example", "parent": 1}),
+        ])
+        .await;
         app.posts = vec![sample_post(1, "first")];
         app.list_state.select(Some(0));
-        app.bookmark_selected_post();
+        app.open_comments_for_selected();
+        handle_events_until(&mut app, |event| {
+            matches!(event, AppEvent::LoadCommentsComplete { .. })
+        })
+        .await;
+        assert_eq!(app.focus_pane, Pane::Comments);
 
-        app.focus_previous_pane();
-        assert_eq!(app.focus_pane, Pane::Posts);
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('f')))
+            .unwrap();
+        assert!(app.link_picker.is_some());
+        app.link_picker = None;
 
-        app.focus_previous_pane();
-        assert_eq!(app.focus_pane, Pane::Bookmarks);
+        assert_eq!(
+            copy_with_key(&mut app, 'y').await,
+            ("Copied comment link".to_string(), discussion_url(2))
+        );
+    }
 
-        app.focus_previous_pane();
-        assert_eq!(app.focus_pane, Pane::Feeds);
+    #[tokio::test]
+    async fn n_jumps_to_the_next_comment_by_the_submitter() {
+        let mut app = App::with_paths(None, None);
+        app.comments_open = true;
+        app.focus_pane = Pane::Comments;
+        app.viewed_post = Some(sample_post(1, "Ask HN"));
+        app.comments_for_post_id = Some(1);
+        app.comments = vec![
+            sample_comment("alice", "one"),
+            sample_comment("author", "two"),
+            sample_comment("bob", "three"),
+        ];
+        app.comment_start_lines = vec![0, 2, 4];
+        app.comment_line_count = 20;
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('n')))
+            .unwrap();
+        assert_eq!(app.focused_comment_index(), Some(1));
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('n')))
+            .unwrap();
+        assert_eq!(app.focused_comment_index(), Some(1));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No more comments from OP")
+        );
     }
 
     #[tokio::test]
-    async fn posts_pane_supports_vim_style_jk_navigation() {
-        let mut app = App::new();
-        app.posts = vec![sample_post(1, "first"), sample_post(2, "second")];
-        app.list_state.select(Some(0));
+    async fn thread_jumps_follow_the_comment_tree() {
+        let mut app = App::with_paths(None, None);
+        app.comments_open = true;
+        app.focus_pane = Pane::Comments;
+        let mut comments = vec![
+            sample_comment("alice", "root"),
+            sample_comment("bob", "reply"),
+            sample_comment("carol", "nested"),
+            sample_comment("dave", "next thread"),
+        ];
+        comments[1].depth = 1;
+        comments[2].depth = 2;
+        app.comments = comments;
+        app.comment_start_lines = vec![0, 2, 4, 6];
+        app.comment_line_count = 20;
+        app.comment_cursor = Some(2);
+        assert_eq!(
+            app.focused_comment_breadcrumbs().as_deref(),
+            Some("alice › bob")
+        );
 
-        app.handle_posts_key(KeyCode::Char('j'));
-        assert_eq!(app.list_state.selected(), Some(1));
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('p')))
+            .unwrap();
+        assert_eq!(app.focused_comment_index(), Some(1));
 
-        app.handle_posts_key(KeyCode::Char('k'));
-        assert_eq!(app.list_state.selected(), Some(0));
+        app.comment_cursor = Some(2);
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('t')))
+            .unwrap();
+        assert_eq!(app.focused_comment_index(), Some(0));
+        assert_eq!(app.focused_comment_breadcrumbs(), None);
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('T')))
+            .unwrap();
+        assert_eq!(app.focused_comment_index(), Some(3));
     }
 
     #[tokio::test]
-    async fn comments_pane_bookmarks_the_post_being_viewed() {
-        let mut app = App::new();
-        app.posts = vec![sample_post(1, "first"), sample_post(2, "second")];
+    async fn comment_cursor_stays_put_while_scrolling_and_is_kept_in_view() {
+        let mut app = App::with_paths(None, None);
         app.comments_open = true;
-        app.comments_for_post_id = Some(1);
         app.focus_pane = Pane::Comments;
-        app.list_state.select(Some(1));
+        app.comments = vec![
+            sample_comment("alice", "one"),
+            sample_comment("bob", "two"),
+            sample_comment("carol", "three"),
+        ];
+        app.comment_start_lines = vec![0, 4, 8];
+        app.comment_line_count = 12;
+        app.comments_viewport_height = 6;
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Down)).unwrap();
+        assert_eq!(app.focused_comment_index(), Some(0));
+        assert_eq!(app.comments_scroll, 0);
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('j')))
+            .unwrap();
+        assert_eq!(app.comments_scroll, 1);
+        assert_eq!(app.focused_comment_index(), Some(0));
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Down)).unwrap();
+        assert_eq!(app.focused_comment_index(), Some(1));
+        assert_eq!(app.comments_scroll, 2);
+
+        app.comments_scroll = 6;
+        app.handle_key_event(KeyEvent::from(KeyCode::Up)).unwrap();
+        assert_eq!(app.focused_comment_index(), Some(0));
+        assert_eq!(app.comments_scroll, 0);
+    }
 
-        app.handle_comments_key(KeyCode::Char('b'));
+    #[tokio::test]
+    async fn s_cycles_the_comment_order() {
+        let mut app = App::with_paths(None, None);
+        app.comments_open = true;
+        app.focus_pane = Pane::Comments;
+        app.comments = vec![sample_comment("alice", "one")];
+        app.comment_cursor = Some(3);
+        app.comments_thread = Some(CommentThread::default());
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('s')))
+            .unwrap();
+        assert_eq!(app.comment_order, CommentOrder::Oldest);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Comments: oldest first")
+        );
+        assert_eq!(app.comment_cursor, Some(0));
 
-        assert_eq!(app.bookmarks.len(), 1);
-        assert_eq!(app.bookmarks[0].id, 1);
+        for _ in 1..CommentOrder::ALL.len() {
+            app.handle_key_event(KeyEvent::from(KeyCode::Char('s')))
+                .unwrap();
+        }
+        assert_eq!(app.comment_order, CommentOrder::Ranked);
     }
 
     #[tokio::test]
-    async fn opening_comments_uses_fresh_cache_without_fetch() {
-        let mut app = App::new();
-        app.posts = vec![sample_post(1, "first")];
+    async fn posts_sort_and_filters_apply_per_feed_and_to_new_pages() {
+        let mut app = App::with_paths(None, None);
+        let post = |id, points| Post {
+            points,
+            published_at: App::unix_now(),
+            ..sample_post(id, "post")
+        };
+        app.feed_posts = vec![post(1, 5), post(2, 40), post(3, 20)];
+        app.apply_posts_view();
         app.list_state.select(Some(0));
-        app.comments_cache.insert(
-            1,
-            CachedComments {
-                comments: vec![sample_comment("alice", "cached")],
-                fetched_at: Instant::now(),
+        let ids = |app: &App| app.posts.iter().map(|post| post.id).collect::<Vec<_>>();
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('s')))
+            .unwrap();
+        assert_eq!(ids(&app), vec![2, 3, 1]);
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('p')))
+            .unwrap();
+        assert_eq!(ids(&app), vec![2, 3]);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Showing posts by points, ≥10 points")
+        );
+
+        let mut item = base_item(4);
+        item.title = Some("late".to_string());
+        item.url = Some("https://example.com/4".to_string());
+        item.kind = Some("story".to_string());
+        item.score = Some(30);
+        item.time = Some(App::unix_now());
+        app.active_posts_request_id = Some(1);
+        app.handle_app_event(AppEvent::PostsFetched {
+            request_id: 1,
+            result: Ok(PostsFetchResult {
+                mode: PostsFetchMode::Append,
+                story_ids: None,
+                items: vec![item],
+                failed: vec![5, 6],
+                next_story_index: 6,
+            }),
+        });
+        assert_eq!(ids(&app), vec![2, 4, 3]);
+        assert_eq!(app.failed_items, vec![5, 6]);
+
+        assert!(app.saved_state.posts_views.contains_key("top"));
+        app.selected_feed = FeedTab::New;
+        assert!(app.posts_view().is_default());
+        app.selected_feed = FeedTab::Top;
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('z')))
+            .unwrap();
+        assert_eq!(ids(&app), vec![1, 2, 3, 4]);
+        assert!(app.saved_state.posts_views.is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_posts_requests_explain_the_error_unless_cancelled() {
+        let mut app = App::with_paths(None, None);
+        app.active_posts_request_id = Some(1);
+        app.handle_app_event(AppEvent::PostsFetched {
+            request_id: 1,
+            result: Err(FetchError::Cancelled),
+        });
+        assert!(app.posts_notice.is_none());
+
+        app.active_posts_request_id = Some(2);
+        app.handle_app_event(AppEvent::PostsFetched {
+            request_id: 2,
+            result: Err(FetchError::Offline),
+        });
+        assert_eq!(
+            app.posts_notice.as_deref(),
+            Some(
+                "Failed to load posts: could not reach Hacker News, check your connection and try again"
+            )
+        );
+
+        app.posts = vec![sample_post(1, "kept")];
+        app.active_posts_request_id = Some(3);
+        app.handle_app_event(AppEvent::PostsFetched {
+            request_id: 3,
+            result: Err(FetchError::Status(StatusCode::NOT_FOUND)),
+        });
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Failed to load posts: Hacker News responded with 404 Not Found")
+        );
+    }
+
+    #[tokio::test]
+    async fn shift_r_retries_only_the_posts_that_failed_to_load() {
+        let mut app = App::with_paths(None, None);
+        app.hn_client = api_fixture(vec![json!({"id": 2, "type": "story", "title": "late"})]).await;
+        app.story_ids = vec![1, 2, 3];
+        app.next_story_index = 3;
+        app.feed_posts = vec![sample_post(1, "first"), sample_post(3, "third")];
+        app.failed_items = vec![2];
+        app.apply_posts_view();
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT))
+            .unwrap();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Retrying failed posts (1)")
+        );
+        handle_events_until(&mut app, |event| {
+            matches!(event, AppEvent::PostsFetched { .. })
+        })
+        .await;
+
+        let ids: Vec<u64> = app.posts.iter().map(|post| post.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert!(app.failed_items.is_empty());
+        assert_eq!(app.next_story_index, 3);
+    }
+
+    #[tokio::test]
+    async fn partially_retried_posts_return_to_their_rank_under_the_feed_sort() {
+        let mut app = App::with_paths(None, None);
+        app.hn_client = api_fixture(vec![
+            json!({"id": 2, "type": "story", "title": "second", "score": 50}),
+            json!({"id": 4, "type": "story", "title": "fourth", "score": 5}),
+        ])
+        .await;
+        app.story_ids = vec![1, 2, 3, 4, 5, 6];
+        app.next_story_index = 6;
+        let mut posts: Vec<Post> = [1, 3, 6]
+            .into_iter()
+            .map(|id| sample_post(id, "post"))
+            .collect();
+        posts[0].points = 10;
+        posts[1].points = 10;
+        posts[2].points = 1;
+        app.feed_posts = posts;
+        app.failed_items = vec![2, 4, 5];
+        app.saved_state.posts_views.insert(
+            app.selected_feed.label().to_string(),
+            PostsView {
+                sort: PostSort::Points,
+                min_points: Some(5),
+                ..PostsView::default()
             },
         );
+        app.apply_posts_view();
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT))
+            .unwrap();
+        handle_events_until(&mut app, |event| {
+            matches!(event, AppEvent::PostsFetched { .. })
+        })
+        .await;
+
+        let ids: Vec<u64> = app.feed_posts.iter().map(|post| post.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4, 6]);
+        assert_eq!(app.failed_items, vec![5]);
+        let ids: Vec<u64> = app.posts.iter().map(|post| post.id).collect();
+        assert_eq!(ids, vec![2, 1, 3, 4]);
+    }
 
-        app.open_comments_for_selected();
+    #[tokio::test]
+    async fn r_refreshes_the_feed_even_with_failed_posts() {
+        let mut app = App::with_paths(None, None);
+        app.hn_client = api_fixture(Vec::new()).await;
+        app.feed_posts = vec![sample_post(1, "first")];
+        app.failed_items = vec![2];
+        app.apply_posts_view();
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('r')))
+            .unwrap();
+        handle_events_until(&mut app, |event| matches!(event, AppEvent::Refresh)).await;
+
+        assert!(app.loading);
+        assert_eq!(app.status_message, None);
+
+        app.failed_items.clear();
+        app.loading = false;
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT))
+            .unwrap();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No failed items to retry")
+        );
+    }
 
+    #[tokio::test]
+    async fn shift_r_retries_failed_comments_from_any_pane() {
+        let mut app = App::with_paths(None, None);
+        app.hn_client = api_fixture(vec![
+            json!({"id": 1, "type": "story", "title": "first", "kids": [2, 3], "descendants": 2}),
+            json!({"id": 2, "type": "comment", "by": "alice", "text": "hi", "parent": 1}),
+        ])
+        .await;
+        app.posts = vec![sample_post(1, "first")];
+        app.list_state.select(Some(0));
+        app.open_comments_for_selected();
+        handle_events_until(&mut app, |event| {
+            matches!(event, AppEvent::LoadCommentsComplete { .. })
+        })
+        .await;
+        assert_eq!(app.comments.len(), 1);
+        assert_eq!(app.failed_comment_count(), 1);
+
+        app.set_focus_pane(Pane::Posts);
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('R'), KeyModifiers::SHIFT))
+            .unwrap();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Retrying failed comments (1)")
+        );
         assert!(app.comments_open);
-        assert_eq!(app.comments_for_post_id, Some(1));
-        assert!(!app.comments_loading);
+        assert!(app.comments_loading_more);
+        handle_events_until(&mut app, |event| {
+            matches!(event, AppEvent::MoreCommentsLoaded { .. })
+        })
+        .await;
         assert_eq!(app.comments.len(), 1);
-        assert_eq!(app.comments[0].text, "cached");
+        assert_eq!(app.failed_comment_count(), 1);
+    }
+
+    /// Presses `key` with a clipboard command that saves the copied text, and
+    /// returns the status message and the text.
+    async fn copy_with_key(app: &mut App, key: char) -> (String, String) {
+        let path = env::temp_dir().join(format!("lazynews-app-copy-{}", std::process::id()));
+        app.config.clipboard_command = Some(format!("cat > '{}'", path.display()));
+        app.osc52_supported = false;
+        app.handle_key_event(KeyEvent::from(KeyCode::Char(key)))
+            .unwrap();
+        handle_events_until(app, |event| matches!(event, AppEvent::CopyFinished { .. })).await;
+        let copied = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        (app.status_message.clone().unwrap_or_default(), copied)
     }
 
     #[tokio::test]
-    async fn opening_comments_with_stale_cache_keeps_comments_and_refreshes() {
-        let mut app = App::new();
-        app.posts = vec![sample_post(1, "first")];
+    async fn y_copies_post_and_comment_links_and_text() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(7, "first")];
         app.list_state.select(Some(0));
-        app.comments_cache.insert(
-            1,
-            CachedComments {
-                comments: vec![sample_comment("alice", "cached")],
-                fetched_at: Instant::now()
-                    - Duration::from_secs(COMMENTS_CACHE_REFRESH_AFTER_SECS + 1),
-            },
+        assert_eq!(
+            copy_with_key(&mut app, 'y').await,
+            (
+                "Copied post URL".to_string(),
+                "https://example.com/7".to_string()
+            )
+        );
+        assert_eq!(
+            copy_with_key(&mut app, 'Y').await,
+            ("Copied discussion URL".to_string(), discussion_url(7))
         );
 
-        app.open_comments_for_selected();
+        app.comments_open = true;
+        app.set_focus_pane(Pane::Comments);
+        app.comments = vec![Comment {
+            id: 9,
+            ..sample_comment("alice", "hello there")
+        }];
+        app.comment_cursor = Some(0);
+        assert_eq!(
+            copy_with_key(&mut app, 'y').await,
+            ("Copied comment link".to_string(), discussion_url(9))
+        );
+        assert_eq!(
+            copy_with_key(&mut app, 'Y').await,
+            ("Copied comment text".to_string(), "hello there".to_string())
+        );
+    }
 
-        assert!(app.comments_open);
-        assert_eq!(app.comments_for_post_id, Some(1));
-        assert!(app.comments_loading);
-        assert_eq!(app.comments.len(), 1);
-        assert_eq!(app.comments[0].text, "cached");
+    #[tokio::test]
+    async fn reader_command_output_is_shown_in_the_reader() {
+        let mut app = App::with_paths(None, None);
+        app.config.reader_command = Some("printf 'Dumped %s'".to_string());
+        app.feed_posts = vec![sample_post(7, "first")];
+        app.apply_posts_view();
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('v')))
+            .unwrap();
+        handle_events_until(&mut app, |event| {
+            matches!(event, AppEvent::ArticleFetched { .. })
+        })
+        .await;
+
+        let reader = app.reader.as_ref().unwrap();
+        assert_eq!(reader.title, "first");
+        assert_eq!(
+            reader.article.as_ref().unwrap().blocks,
+            vec![ArticleBlock::Preformatted(format!("Dumped {}", reader.url))]
+        );
+        assert_eq!(app.focus_pane, Pane::Reader);
     }
 
     #[tokio::test]
-    async fn failed_comments_refresh_keeps_cached_comments_visible() {
-        let mut app = App::new();
+    async fn clipboard_command_is_only_a_fallback_for_osc52() {
+        let path = env::temp_dir().join(format!("lazynews-app-osc52-{}", std::process::id()));
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(7, "first")];
+        app.list_state.select(Some(0));
+        app.config.clipboard_command = Some(format!("cat > '{}'", path.display()));
+        app.osc52_supported = true;
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('y')))
+            .unwrap();
+
+        assert_eq!(app.status_message.as_deref(), Some("Copied post URL"));
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn failed_copy_commands_are_reported() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(7, "first")];
+        app.list_state.select(Some(0));
+        app.config.clipboard_command = Some("false".to_string());
+        app.osc52_supported = false;
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('y')))
+            .unwrap();
+        handle_events_until(&mut app, |event| {
+            matches!(event, AppEvent::CopyFinished { .. })
+        })
+        .await;
+
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Failed to copy post URL: `false` exited with exit status: 1")
+        );
+    }
+
+    #[tokio::test]
+    async fn link_picker_lists_focused_comment_links_and_closes_on_hint() {
+        let mut app = App::with_paths(None, None);
         app.comments_open = true;
-        app.comments_for_post_id = Some(1);
-        app.comments_loading = true;
-        app.comments = vec![sample_comment("alice", "cached")];
-        app.comments_cache.insert(
-            1,
-            CachedComments {
-                comments: vec![sample_comment("alice", "cached")],
-                fetched_at: Instant::now()
-                    - Duration::from_secs(COMMENTS_CACHE_REFRESH_AFTER_SECS + 1),
+        app.focus_pane = Pane::Comments;
+        app.comments = vec![
+            sample_comment("alice", "no links here"),
+            sample_comment("bob", "see https://a.example and https://b.example"),
+        ];
+        app.comment_start_lines = vec![0, 4];
+        app.comment_line_count = 8;
+        app.comment_cursor = Some(0);
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('f')))
+            .unwrap();
+        assert!(app.link_picker.is_none());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No links in this comment")
+        );
+
+        app.comment_cursor = Some(1);
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('f')))
+            .unwrap();
+        let picker = app.link_picker.as_ref().expect("picker should open");
+        assert_eq!(picker.visible().len(), 2);
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('j')))
+            .unwrap();
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('2')))
+            .unwrap();
+        assert!(app.link_picker.is_none());
+        assert_eq!(app.focus_pane, Pane::Comments);
+    }
+
+    #[tokio::test]
+    async fn reader_uses_cached_article_and_closes_back_to_posts() {
+        let mut app = App::with_paths(None, None);
+        let post = sample_post(1, "first");
+        app.posts = vec![post.clone()];
+        app.list_state.select(Some(0));
+        app.cache_article(
+            post.url.clone(),
+            Article {
+                title: None,
+                blocks: vec![ArticleBlock::Paragraph("cached body".to_string())],
             },
         );
 
-        app.handle_app_event(AppEvent::LoadCommentsComplete {
-            post_id: 1,
-            result: Err("network down".to_string()),
-        });
+        app.handle_posts_key(KeyCode::Char('v'));
 
-        assert!(!app.comments_loading);
-        assert_eq!(app.comments.len(), 1);
-        assert_eq!(app.comments[0].text, "cached");
-        assert!(app.comments_error.is_none());
+        assert_eq!(app.focus_pane, Pane::Reader);
+        let reader = app.reader.as_ref().expect("reader should open");
+        assert!(!reader.loading());
+        assert!(app.visible_panes().contains(&Pane::Reader));
+
+        app.set_focus_pane(Pane::Posts);
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('5')))
+            .unwrap();
+        assert_eq!(app.focus_pane, Pane::Reader);
+
+        app.handle_reader_key(KeyCode::Esc);
+        assert!(app.reader.is_none());
+        assert_eq!(app.focus_pane, Pane::Posts);
     }
 
     #[tokio::test]
-    async fn feed_switching_requires_feeds_focus() {
-        let mut app = App::new();
-        assert_eq!(app.selected_feed, FeedTab::Top);
-
-        app.handle_posts_key(KeyCode::Right);
-        assert_eq!(app.selected_feed, FeedTab::Top);
+    async fn article_cache_drops_the_least_recently_read_article() {
+        let mut app = App::with_paths(None, None);
+        let article = |text: &str| Article {
+            title: None,
+            blocks: vec![ArticleBlock::Paragraph(text.to_string())],
+        };
+        for index in 0..MAX_CACHED_ARTICLES {
+            app.cache_article(format!("https://example.com/{index}"), article("body"));
+        }
 
-        app.handle_feeds_key(KeyCode::Right);
-        assert_eq!(app.selected_feed, FeedTab::New);
+        assert!(app.cached_article("https://example.com/0").is_some());
+        app.cache_article("https://example.com/new".to_string(), article("new"));
 
-        app.handle_feeds_key(KeyCode::Left);
-        assert_eq!(app.selected_feed, FeedTab::Top);
+        assert_eq!(app.article_cache.len(), MAX_CACHED_ARTICLES);
+        assert!(app.cached_article("https://example.com/1").is_none());
+        assert!(app.cached_article("https://example.com/0").is_some());
+        assert_eq!(
+            app.cached_article("https://example.com/new"),
+            Some(article("new"))
+        );
     }
 
     #[tokio::test]
-    async fn enter_in_feeds_pane_moves_focus_to_posts() {
-        let mut app = App::new();
-        app.focus_pane = Pane::Feeds;
+    async fn reader_ignores_stale_article_results() {
+        let mut app = App::with_paths(None, None);
+        let mut post = sample_post(1, "first");
+        post.url = "https://example.com/current".to_string();
+        app.posts = vec![post];
+        app.list_state.select(Some(0));
+        app.handle_posts_key(KeyCode::Char('v'));
 
-        app.handle_feeds_key(KeyCode::Enter);
+        app.handle_article_fetched(
+            "https://example.com/other".to_string(),
+            Err("boom".to_string()),
+        );
+        assert!(app.reader.as_ref().is_some_and(ReaderView::loading));
 
-        assert_eq!(app.focus_pane, Pane::Posts);
+        app.handle_article_fetched(
+            "https://example.com/current".to_string(),
+            Err("Reader mode only supports HTML pages (content type: application/pdf)".to_string()),
+        );
+        let reader = app.reader.as_ref().unwrap();
+        assert!(
+            reader
+                .error
+                .as_deref()
+                .is_some_and(|error| error.contains("application/pdf"))
+        );
     }
 
     #[tokio::test]
-    async fn pane_shortcuts_focus_panes() {
-        let mut app = App::new();
-        app.focus_pane = Pane::Feeds;
-        app.comments_open = true;
+    async fn author_profile_popup_loads_and_opens_submissions() {
+        let mut app = App::with_paths(None, None);
+        let mut post = sample_post(1, "first");
+        post.author = "alice".to_string();
+        app.posts = vec![post];
+        app.list_state.select(Some(0));
 
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE))
-            .expect("pane key should be handled");
-        assert_eq!(app.focus_pane, Pane::Posts);
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('u')))
+            .unwrap();
+        assert!(app.profile.as_ref().is_some_and(ProfileView::is_loading));
 
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('4'), KeyModifiers::NONE))
-            .expect("pane key should be handled");
-        assert_eq!(app.focus_pane, Pane::Feeds);
+        let mut story = base_item(7);
+        story.title = Some("Alice's story".to_string());
+        story.url = Some("https://example.com/alice".to_string());
+        story.kind = Some("story".to_string());
+        let profile = UserProfile {
+            user: User {
+                id: "alice".to_string(),
+                karma: 10,
+                created: 0,
+                about: None,
+                submitted: vec![7],
+            },
+            submissions: vec![story],
+        };
+        app.handle_app_event(AppEvent::UserProfileFetched {
+            username: "bob".to_string(),
+            result: Ok(None),
+        });
+        assert!(app.profile.as_ref().is_some_and(ProfileView::is_loading));
+        app.handle_app_event(AppEvent::UserProfileFetched {
+            username: "alice".to_string(),
+            result: Ok(Some(Box::new(profile))),
+        });
 
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('3'), KeyModifiers::NONE))
-            .expect("pane key should be handled");
-        assert_eq!(app.focus_pane, Pane::Comments);
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter))
+            .unwrap();
+
+        assert!(app.profile.is_none());
+        assert!(app.comments_open);
+        assert_eq!(app.comments_for_post_id, Some(7));
     }
 
     #[tokio::test]
-    async fn feed_switch_refresh_keeps_feeds_focus() {
-        let mut app = App::new();
-        app.focus_pane = Pane::Feeds;
-
-        app.handle_feeds_key(KeyCode::Right);
-        app.handle_app_event(AppEvent::Refresh);
+    async fn watch_scans_raise_alerts_once_and_badge_posts() {
+        let mut app = App::with_paths(None, None);
+        let rules = vec![WatchRule {
+            keyword: Some("rust".to_string()),
+            ..Default::default()
+        }];
+        app.watch_list = WatchList::new(&rules).0;
+        app.posts = vec![sample_post(5, "Rust in production")];
+
+        let mut matching = base_item(5);
+        matching.title = Some("Rust in production".to_string());
+        matching.kind = Some("story".to_string());
+        let mut other = base_item(6);
+        other.title = Some("Go generics".to_string());
+        other.kind = Some("story".to_string());
+        app.handle_app_event(AppEvent::WatchScanFinished {
+            result: Ok(vec![matching.clone(), other]),
+        });
+        app.handle_app_event(AppEvent::WatchScanFinished {
+            result: Ok(vec![matching]),
+        });
 
-        assert_eq!(app.selected_feed, FeedTab::New);
-        assert_eq!(app.focus_pane, Pane::Feeds);
+        assert_eq!(app.alerts.len(), 1);
+        assert_eq!(app.alerts[0].rule, "rust");
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Watch alert: Rust in production")
+        );
+        assert!(app.visible_panes().contains(&Pane::Alerts));
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('6')))
+            .unwrap();
+        assert_eq!(app.focus_pane, Pane::Alerts);
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter))
+            .unwrap();
+        assert_eq!(app.comments_for_post_id, Some(5));
+
+        app.set_focus_pane(Pane::Alerts);
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('d')))
+            .unwrap();
+        assert!(app.alerts.is_empty());
+        assert_eq!(app.focus_pane, Pane::Posts);
     }
 
     #[tokio::test]
-    async fn key_one_toggles_bookmarks_when_focused() {
-        let mut app = App::new();
-        app.posts = vec![sample_post(1, "first")];
-        app.list_state.select(Some(0));
-        app.bookmark_selected_post();
-        app.focus_pane = Pane::Bookmarks;
-        app.bookmarks_collapsed = false;
+    async fn dismissed_alerts_are_not_raised_again() {
+        let mut app = App::with_paths(None, None);
+        let rules = vec![WatchRule {
+            keyword: Some("rust".to_string()),
+            ..Default::default()
+        }];
+        app.watch_list = WatchList::new(&rules).0;
+        let mut matching = base_item(5);
+        matching.title = Some("Rust in production".to_string());
+        matching.url = Some("https://example.com/5".to_string());
+        matching.kind = Some("story".to_string());
+
+        app.handle_app_event(AppEvent::WatchScanFinished {
+            result: Ok(vec![matching.clone()]),
+        });
+        assert_eq!(app.alerts.len(), 1);
+        app.set_focus_pane(Pane::Alerts);
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('d')))
+            .unwrap();
+        assert!(app.alerts.is_empty());
+
+        app.status_message = None;
+        app.handle_app_event(AppEvent::WatchScanFinished {
+            result: Ok(vec![matching.clone()]),
+        });
+        app.active_posts_request_id = Some(1);
+        app.handle_app_event(AppEvent::PostsFetched {
+            request_id: 1,
+            result: Ok(PostsFetchResult {
+                mode: PostsFetchMode::Replace,
+                story_ids: Some(vec![5]),
+                items: vec![matching],
+                failed: Vec::new(),
+                next_story_index: 1,
+            }),
+        });
 
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('1'), KeyModifiers::NONE))
-            .expect("pane key should be handled");
-        assert!(app.bookmarks_collapsed);
-        assert_eq!(app.focus_pane, Pane::Bookmarks);
+        assert!(app.alerts.is_empty());
+        assert_eq!(app.status_message, None);
+        assert_eq!(app.posts.len(), 1);
+    }
 
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('1'), KeyModifiers::NONE))
-            .expect("pane key should be handled");
-        assert!(!app.bookmarks_collapsed);
-        assert_eq!(app.focus_pane, Pane::Bookmarks);
+    #[tokio::test]
+    async fn domain_filter_toggles_from_the_selected_post() {
+        let mut app = App::with_paths(None, None);
+        let mut other = sample_post(2, "elsewhere");
+        other.domain = "rust-lang.org".to_string();
+        app.feed_posts = vec![sample_post(1, "first"), other, sample_post(3, "third")];
+        app.apply_posts_view();
+        app.list_state.select(Some(2));
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('f')))
+            .unwrap();
+        assert_eq!(app.domain_filter.as_deref(), Some("example.com"));
+        assert_eq!(
+            app.posts.iter().map(|post| post.id).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert_eq!(app.selected_post().map(|post| post.id), Some(3));
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('f')))
+            .unwrap();
+        assert_eq!(app.domain_filter, None);
+        assert_eq!(app.posts.len(), 3);
+        assert_eq!(app.selected_post().map(|post| post.id), Some(3));
+
+        let mut ask = base_item(4);
+        ask.title = Some("Ask HN: question".to_string());
+        ask.url = Some(discussion_url(4));
+        ask.kind = Some("story".to_string());
+        let posts = App::posts_from_items(vec![ask], &MuteFilter::default());
+        assert_eq!(posts[0].domain, "self");
     }
 
     #[tokio::test]
-    async fn navigating_away_from_bookmarks_collapses_it() {
-        let mut app = App::new();
-        app.posts = vec![sample_post(1, "first")];
+    async fn muting_hides_posts_and_collapses_comments() {
+        let mut app = App::with_paths(None, None);
+        let mut first = sample_post(1, "first");
+        first.author = "alice".to_string();
+        let mut second = sample_post(2, "second");
+        second.author = "bob".to_string();
+        second.url = "https://rust-lang.org/2".to_string();
+        second.domain = "rust-lang.org".to_string();
+        app.feed_posts = vec![first, second, sample_post(3, "third")];
+        app.apply_posts_view();
         app.list_state.select(Some(0));
-        app.bookmark_selected_post();
-        app.open_bookmarks_pane();
 
-        assert_eq!(app.focus_pane, Pane::Bookmarks);
-        assert!(!app.bookmarks_collapsed);
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('m')))
+            .unwrap();
+        assert_eq!(app.status_message.as_deref(), Some("Muted alice"));
+        assert_eq!(
+            app.posts.iter().map(|post| post.id).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
 
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE))
-            .expect("pane key should be handled");
+        app.list_state.select(Some(1));
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('M')))
+            .unwrap();
+        assert_eq!(
+            app.saved_state.mutes.domains,
+            vec!["example.com".to_string()]
+        );
+        assert_eq!(
+            app.posts.iter().map(|post| post.id).collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert_eq!(app.list_state.selected(), Some(0));
 
-        assert_eq!(app.focus_pane, Pane::Posts);
-        assert!(app.bookmarks_collapsed);
+        let mut comment = sample_comment("bob", "hello");
+        comment.id = 9;
+        app.comments_open = true;
+        app.comments_for_post_id = Some(2);
+        app.comments = vec![comment];
+        app.comment_start_lines = vec![0];
+        app.comment_cursor = Some(0);
+        app.focus_pane = Pane::Comments;
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('m')))
+            .unwrap();
+        assert!(app.comments[0].muted);
+        assert!(app.posts.is_empty());
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('x')))
+            .unwrap();
+        assert_eq!(app.revealed_comment_ids, vec![9]);
     }
 
     #[tokio::test]
-    async fn tab_focus_to_bookmarks_expands_it() {
-        let mut app = App::new();
-        app.posts = vec![sample_post(1, "first")];
-        app.list_state.select(Some(0));
-        app.bookmark_selected_post();
-        app.focus_pane = Pane::Posts;
-        app.bookmarks_collapsed = true;
-
-        app.handle_key_event(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT))
-            .expect("pane key should be handled");
-
-        assert_eq!(app.focus_pane, Pane::Bookmarks);
-        assert!(!app.bookmarks_collapsed);
+    async fn following_users_from_the_profile_popup() {
+        let mut app = App::with_paths(None, None);
+        app.profile = Some(ProfileView::loading("alice"));
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('f')))
+            .unwrap();
+        assert!(app.saved_state.is_followed("alice"));
+        assert_eq!(app.status_message.as_deref(), Some("Following alice"));
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('f')))
+            .unwrap();
+        assert!(app.saved_state.followed_users.is_empty());
+        assert_eq!(app.status_message.as_deref(), Some("Unfollowed alice"));
     }
 
     #[tokio::test]
-    async fn deleting_bookmarks_updates_focus_and_selection() {
-        let mut app = App::new();
-        app.posts = vec![sample_post(1, "first"), sample_post(2, "second")];
-        app.list_state.select(Some(0));
-        app.bookmark_selected_post();
-        app.list_state.select(Some(1));
-        app.bookmark_selected_post();
-        app.focus_pane = Pane::Bookmarks;
-        app.bookmarks_collapsed = false;
-        app.bookmarks_state.select(Some(0));
+    async fn following_feed_explains_when_nobody_is_followed() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first")];
+        app.selected_feed = FeedTab::Following;
 
-        app.handle_bookmarks_key(KeyCode::Char('d'));
-        assert_eq!(app.bookmarks.len(), 1);
-        assert_eq!(app.bookmarks[0].id, 2);
-        assert_eq!(app.bookmarks_state.selected(), Some(0));
-        assert_eq!(app.focus_pane, Pane::Bookmarks);
+        app.refresh_posts();
 
-        app.handle_bookmarks_key(KeyCode::Char('d'));
-        assert!(app.bookmarks.is_empty());
-        assert!(!app.bookmarks_visible());
-        assert_eq!(app.focus_pane, Pane::Posts);
+        assert!(app.posts.is_empty());
+        assert!(!app.loading);
+        assert!(!app.has_more_posts);
+        assert_eq!(app.posts_notice.as_deref(), Some(NO_FOLLOWED_USERS_NOTICE));
     }
 
     #[tokio::test]
-    async fn opening_all_bookmarks_keeps_bookmark_state() {
-        let mut app = App::new();
-        app.posts = vec![sample_post(1, "first"), sample_post(2, "second")];
+    async fn enter_from_bookmarks_opens_comments_for_selected_post() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first")];
         app.list_state.select(Some(0));
         app.bookmark_selected_post();
-        app.list_state.select(Some(1));
-        app.bookmark_selected_post();
         app.focus_pane = Pane::Bookmarks;
         app.bookmarks_collapsed = false;
-        app.bookmarks_state.select(Some(1));
-
-        app.handle_bookmarks_key(KeyCode::Char('a'));
+        app.bookmarks_state.select(Some(0));
 
-        assert_eq!(app.bookmarks.len(), 2);
-        assert_eq!(app.bookmarks_state.selected(), Some(1));
-        assert_eq!(app.focus_pane, Pane::Bookmarks);
-        assert!(!app.bookmarks_collapsed);
+        app.handle_bookmarks_key(KeyCode::Enter);
+        assert!(app.comments_open);
+        assert_eq!(app.comments_for_post_id, Some(1));
+        assert_eq!(app.focus_pane, Pane::Comments);
     }
 
     #[tokio::test]
-    async fn enter_from_bookmarks_opens_comments_for_selected_post() {
-        let mut app = App::new();
+    async fn enter_from_bookmarks_opens_posts_missing_from_current_feed() {
+        let mut app = App::with_paths(None, None);
         app.posts = vec![sample_post(1, "first")];
         app.list_state.select(Some(0));
         app.bookmark_selected_post();
+        app.posts = vec![sample_post(2, "second")];
+        app.list_state.select(Some(0));
         app.focus_pane = Pane::Bookmarks;
         app.bookmarks_collapsed = false;
         app.bookmarks_state.select(Some(0));
 
         app.handle_bookmarks_key(KeyCode::Enter);
+
         assert!(app.comments_open);
         assert_eq!(app.comments_for_post_id, Some(1));
+        assert_eq!(
+            app.comments_post().map(|post| post.title.as_str()),
+            Some("first")
+        );
+        assert_eq!(app.list_state.selected(), Some(0));
         assert_eq!(app.focus_pane, Pane::Comments);
     }
 
     #[tokio::test]
     async fn esc_in_bookmarks_collapses_pane_but_keeps_it_visible() {
-        let mut app = App::new();
+        let mut app = App::with_paths(None, None);
         app.posts = vec![sample_post(1, "first")];
         app.list_state.select(Some(0));
         app.bookmark_selected_post();
@@ -1825,7 +4595,7 @@ mod tests {
 
     #[tokio::test]
     async fn enter_expands_collapsed_bookmarks_pane() {
-        let mut app = App::new();
+        let mut app = App::with_paths(None, None);
         app.posts = vec![sample_post(1, "first")];
         app.list_state.select(Some(0));
         app.bookmark_selected_post();
@@ -1837,4 +4607,298 @@ mod tests {
         assert!(!app.bookmarks_collapsed);
         assert_eq!(app.focus_pane, Pane::Bookmarks);
     }
+
+    #[tokio::test]
+    async fn command_palette_captures_keys_until_closed() {
+        let mut app = App::with_paths(None, None);
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char(':'), KeyModifiers::NONE))
+            .expect("palette key should be handled");
+        assert!(app.command_palette.is_some());
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE))
+            .expect("palette key should be handled");
+        assert!(app.running);
+        assert_eq!(
+            app.command_palette.as_ref().map(|palette| palette.query()),
+            Some("q")
+        );
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+            .expect("palette key should be handled");
+        assert!(app.command_palette.is_none());
+    }
+
+    #[tokio::test]
+    async fn command_palette_runs_selected_command() {
+        let mut app = App::with_paths(None, None);
+        app.command_palette = Some(CommandPalette::with_query("feed ask"));
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            .expect("palette key should be handled");
+
+        assert!(app.command_palette.is_none());
+        assert_eq!(app.selected_feed, FeedTab::Ask);
+    }
+
+    #[tokio::test]
+    async fn command_palette_opens_item_from_current_feed() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first"), sample_post(2, "second")];
+        app.list_state.select(Some(0));
+
+        app.run_palette_command(PaletteCommand::OpenItem(2));
+
+        assert_eq!(app.list_state.selected(), Some(1));
+        assert!(app.comments_open);
+        assert_eq!(app.comments_for_post_id, Some(2));
+    }
+
+    #[tokio::test]
+    async fn opening_unlisted_item_waits_for_fetch_then_loads_comments() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first")];
+
+        app.run_palette_command(PaletteCommand::OpenItem(99));
+        assert!(app.comments_open);
+        assert_eq!(app.comments_for_post_id, Some(99));
+        assert!(app.comments_loading);
+        assert!(app.comments_post().is_none());
+
+        let mut item = base_item(99);
+        item.title = Some("Pasted story".to_string());
+        item.url = Some("https://example.com/pasted".to_string());
+        item.kind = Some("story".to_string());
+        app.handle_app_event(AppEvent::ItemFetched {
+            item_id: 99,
+            result: Ok(Box::new(item)),
+        });
+
+        assert_eq!(
+            app.comments_post().map(|post| post.title.as_str()),
+            Some("Pasted story")
+        );
+        assert!(app.comments_loading);
+        assert_eq!(app.focus_pane, Pane::Comments);
+    }
+
+    #[tokio::test]
+    async fn opening_non_story_item_shows_notice() {
+        let mut app = App::with_paths(None, None);
+        app.run_palette_command(PaletteCommand::OpenItem(5));
+
+        let mut item = base_item(5);
+        item.kind = Some("comment".to_string());
+        app.handle_app_event(AppEvent::ItemFetched {
+            item_id: 5,
+            result: Ok(Box::new(item)),
+        });
+
+        assert!(!app.comments_loading);
+        assert_eq!(
+            app.comments_notice.as_deref(),
+            Some("Item 5 is a comment, not a story, job or poll.")
+        );
+    }
+
+    #[tokio::test]
+    async fn more_comments_extend_view_without_resetting_scroll() {
+        let mut app = App::with_paths(None, None);
+        app.comments_open = true;
+        app.comments_for_post_id = Some(1);
+        app.comments = vec![sample_comment("alice", "first")];
+        app.comment_line_count = 30;
+        app.comments_viewport_height = 10;
+        app.comments_thread = Some(CommentThread::default());
+        app.comments_cache.insert(
+            1,
+            CachedComments {
+                comments: app.comments.clone(),
+                thread: None,
+                fetched_at: Instant::now(),
+            },
+        );
+
+        app.handle_comments_key(KeyCode::End);
+        assert!(!app.comments_loading_more);
+        assert_eq!(app.comments_scroll, 20);
+
+        app.comments_loading_more = true;
+        app.handle_app_event(AppEvent::MoreCommentsLoaded {
+            post_id: 1,
+            page: Box::new(CommentsPage {
+                comments: vec![
+                    sample_comment("alice", "first"),
+                    sample_comment("bob", "second"),
+                ],
+                thread: CommentThread::default(),
+            }),
+        });
+
+        assert!(!app.comments_loading_more);
+        assert_eq!(app.comments.len(), 2);
+        assert_eq!(app.comments_cache[&1].comments.len(), 2);
+        assert_eq!(app.comments_scroll, 20);
+    }
+
+    #[tokio::test]
+    async fn scrolling_near_the_end_fetches_the_next_comments_page() {
+        let kids: Vec<u64> = (2..COMMENTS_PAGE_SIZE as u64 + 12).collect();
+        let mut items = vec![json!({"id": 1, "type": "story", "title": "first", "kids": kids})];
+        items.extend(kids.iter().map(
+            |id| json!({"id": id, "type": "comment", "by": "alice", "text": "hi", "parent": 1}),
+        ));
+        let mut app = App::with_paths(None, None);
+        app.hn_client = api_fixture(items).await;
+        app.posts = vec![sample_post(1, "first")];
+        app.list_state.select(Some(0));
+        app.open_comments_for_selected();
+        handle_events_until(&mut app, |event| {
+            matches!(event, AppEvent::LoadCommentsComplete { .. })
+        })
+        .await;
+        assert_eq!(app.comments.len(), COMMENTS_PAGE_SIZE);
+        assert!(app.comments_thread.as_ref().unwrap().has_more());
+
+        app.comment_line_count = 400;
+        app.comments_viewport_height = 10;
+        app.handle_comments_key(KeyCode::End);
+        assert!(app.comments_loading_more);
+        assert_eq!(app.comments_scroll, 390);
+
+        handle_events_until(&mut app, |event| {
+            matches!(event, AppEvent::MoreCommentsLoaded { .. })
+        })
+        .await;
+        assert!(!app.comments_loading_more);
+        assert_eq!(app.comments.len(), kids.len());
+        assert!(!app.comments_thread.as_ref().unwrap().has_more());
+        assert_eq!(app.comments_scroll, 390);
+    }
+
+    #[tokio::test]
+    async fn poll_without_a_body_starts_with_the_cursor_on_the_poll() {
+        let mut app = App::with_paths(None, None);
+        app.hn_client = api_fixture(vec![
+            json!({"id": 1, "type": "poll", "title": "Tabs or spaces?", "parts": [2], "kids": [3]}),
+            json!({"id": 2, "type": "pollopt", "text": "Tabs", "score": 5, "poll": 1}),
+            json!({"id": 3, "type": "comment", "by": "alice", "text": "Spaces", "parent": 1}),
+        ])
+        .await;
+        let mut poll = sample_post(1, "Tabs or spaces?");
+        poll.post_type = PostType::Poll;
+        app.posts = vec![poll];
+        app.list_state.select(Some(0));
+
+        app.open_comments_for_selected();
+        handle_events_until(&mut app, |event| {
+            matches!(event, AppEvent::LoadCommentsComplete { .. })
+        })
+        .await;
+        assert_eq!(app.comments.len(), 1);
+        assert_eq!(app.comment_cursor, None);
+
+        app.close_comments_view();
+        app.open_comments_for_selected();
+        assert_eq!(app.comments.len(), 1);
+        assert_eq!(app.comment_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn streamed_comments_keep_the_selected_order_and_cursor() {
+        let comment = |id: u64, author: &str| Comment {
+            id,
+            ..sample_comment(author, "text")
+        };
+        let mut app = App::with_paths(None, None);
+        app.comments_open = true;
+        app.comments_for_post_id = Some(1);
+        app.comments_loading_more = true;
+        app.comment_order = CommentOrder::Newest;
+        app.comments = vec![comment(3, "carol"), comment(2, "bob")];
+        app.comment_cursor = Some(1);
+
+        let ranked = vec![comment(2, "bob"), comment(4, "dave"), comment(3, "carol")];
+        app.handle_app_event(AppEvent::CommentsProgress {
+            post_id: 1,
+            comments: ranked.clone(),
+        });
+        let ids: Vec<u64> = app.comments.iter().map(|comment| comment.id).collect();
+        assert_eq!(ids, vec![3, 2]);
+        assert_eq!(app.focused_comment().map(|comment| comment.id), Some(2));
+
+        app.comment_order = CommentOrder::Ranked;
+        app.comments = vec![comment(3, "carol"), comment(2, "bob")];
+        app.handle_app_event(AppEvent::CommentsProgress {
+            post_id: 1,
+            comments: ranked,
+        });
+        assert_eq!(app.comments.len(), 3);
+        assert_eq!(app.focused_comment().map(|comment| comment.id), Some(2));
+    }
+
+    #[tokio::test]
+    async fn streamed_comments_fill_in_until_load_completes() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first")];
+        app.list_state.select(Some(0));
+        app.open_comments_for_selected();
+        assert!(app.comments_loading);
+
+        app.handle_app_event(AppEvent::CommentsProgress {
+            post_id: 1,
+            comments: vec![sample_comment("alice", "first")],
+        });
+        assert_eq!(app.comments.len(), 1);
+        assert!(app.comments_loading);
+
+        app.comments_scroll = 3;
+        app.handle_app_event(AppEvent::CommentsProgress {
+            post_id: 1,
+            comments: Vec::new(),
+        });
+        assert_eq!(app.comments.len(), 1);
+
+        app.handle_app_event(AppEvent::LoadCommentsComplete {
+            post_id: 1,
+            result: Ok(Box::new(CommentsPage {
+                comments: vec![
+                    sample_comment("alice", "first"),
+                    sample_comment("bob", "second"),
+                ],
+                thread: CommentThread::default(),
+            })),
+        });
+        assert!(!app.comments_loading);
+        assert_eq!(app.comments.len(), 2);
+        assert_eq!(app.comments_scroll, 3);
+    }
+
+    #[tokio::test]
+    async fn streamed_comments_do_not_replace_stale_cache() {
+        let mut app = App::with_paths(None, None);
+        app.posts = vec![sample_post(1, "first")];
+        app.list_state.select(Some(0));
+        app.comments_cache.insert(
+            1,
+            CachedComments {
+                comments: vec![sample_comment("alice", "cached")],
+                thread: None,
+                fetched_at: Instant::now()
+                    - Duration::from_secs(COMMENTS_CACHE_REFRESH_AFTER_SECS + 1),
+            },
+        );
+        app.open_comments_for_selected();
+
+        app.handle_app_event(AppEvent::CommentsProgress {
+            post_id: 1,
+            comments: vec![
+                sample_comment("bob", "partial"),
+                sample_comment("carol", "partial"),
+            ],
+        });
+
+        assert_eq!(app.comments.len(), 1);
+        assert_eq!(blocks_to_plain_text(&app.comments[0].body), "cached");
+    }
 }
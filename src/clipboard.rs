@@ -0,0 +1,109 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use std::{
+    env,
+    io::{self, Write},
+    process::Stdio,
+};
+use tokio::{io::AsyncWriteExt, process::Command};
+
+/// Copies `text` by writing an OSC 52 sequence to the terminal, which also works
+/// over SSH and inside tmux.
+pub fn copy_osc52(text: &str) -> Result<(), String> {
+    let in_tmux = env::var_os("TMUX").is_some();
+    let mut stdout = io::stdout();
+    stdout
+        .write_all(osc52_sequence(text, in_tmux).as_bytes())
+        .and_then(|()| stdout.flush())
+        .map_err(|error| format!("Failed to write to terminal: {error}"))
+}
+
+/// Whether the terminal is expected to honour OSC 52. macOS Terminal and
+/// VTE-based terminals such as GNOME Terminal silently ignore it.
+pub fn osc52_supported() -> bool {
+    osc52_supported_by(
+        env::var("TERM_PROGRAM").ok().as_deref(),
+        env::var_os("VTE_VERSION").is_some(),
+    )
+}
+
+fn osc52_supported_by(term_program: Option<&str>, is_vte: bool) -> bool {
+    term_program != Some("Apple_Terminal") && !is_vte
+}
+
+pub fn osc52_sequence(text: &str, in_tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    if in_tmux {
+        // tmux only forwards escape sequences wrapped in a DCS passthrough,
+        // with every inner ESC doubled.
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+/// Pipes `text` to `command`, the fallback for terminals that ignore OSC 52.
+pub async fn copy_with_command(command: String, text: String) -> Result<(), String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(&command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|error| format!("Failed to run `{command}`: {error}"))?;
+
+    // A command that exits without reading breaks the pipe; its exit status
+    // says more than the failed write.
+    let written = match child.stdin.take() {
+        Some(mut stdin) => stdin.write_all(text.as_bytes()).await,
+        None => Ok(()),
+    };
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|error| format!("Failed to run `{command}`: {error}"))?;
+    if !status.success() {
+        return Err(format!("`{command}` exited with {status}"));
+    }
+    written.map_err(|error| format!("Failed to write to `{command}`: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn osc52_sequence_is_base64_and_wrapped_for_tmux() {
+        assert_eq!(osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn osc52_is_skipped_for_terminals_that_ignore_it() {
+        assert!(osc52_supported_by(Some("iTerm.app"), false));
+        assert!(osc52_supported_by(None, false));
+        assert!(!osc52_supported_by(Some("Apple_Terminal"), false));
+        assert!(!osc52_supported_by(None, true));
+    }
+
+    #[tokio::test]
+    async fn copy_command_receives_the_text_and_reports_failures() {
+        let path = env::temp_dir().join(format!("lazynews-clipboard-{}", std::process::id()));
+        let command = format!("cat > '{}'", path.display());
+
+        copy_with_command(command, "copied".to_string())
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "copied");
+        let _ = std::fs::remove_file(&path);
+
+        let error = copy_with_command("false".to_string(), "copied".to_string())
+            .await
+            .unwrap_err();
+        assert!(error.contains("exited"), "{error}");
+    }
+}
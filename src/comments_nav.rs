@@ -1,30 +1,14 @@
 use crate::hn::Comment;
-
-pub fn current_index_from_scroll(
-    comment_start_lines: &[u16],
-    comments_len: usize,
-    comments_scroll: u16,
-) -> Option<usize> {
-    if comments_len == 0 || comment_start_lines.is_empty() {
-        return None;
-    }
-
-    let mut current = 0usize;
-    for (index, line) in comment_start_lines.iter().enumerate() {
-        if *line > comments_scroll {
-            break;
-        }
-        current = index;
-    }
-
-    Some(current.min(comments_len.saturating_sub(1)))
-}
+use std::cmp::Ordering;
 
 pub fn next_sibling_or_outer_index(comments: &[Comment], current_index: usize) -> Option<usize> {
     let current_depth = comments.get(current_index)?.depth;
 
     for (next_index, comment) in comments.iter().enumerate().skip(current_index + 1) {
-        if comment.depth == current_depth || comment.depth < current_depth {
+        if matches!(
+            comment.depth.cmp(&current_depth),
+            Ordering::Equal | Ordering::Less
+        ) {
             return Some(next_index);
         }
     }
@@ -68,7 +52,23 @@ pub fn previous_comment_index(current_index: usize) -> Option<usize> {
     }
 }
 
-fn nearest_parent_index(comments: &[Comment], current_index: usize) -> Option<usize> {
+/// The first comment by `author` after `current_index`, or from the top when
+/// no comment is focused.
+pub fn next_author_index(
+    comments: &[Comment],
+    current_index: Option<usize>,
+    author: &str,
+) -> Option<usize> {
+    let start = current_index.map_or(0, |index| index + 1);
+    comments
+        .iter()
+        .enumerate()
+        .skip(start)
+        .find(|(_, comment)| comment.author == author)
+        .map(|(index, _)| index)
+}
+
+pub fn nearest_parent_index(comments: &[Comment], current_index: usize) -> Option<usize> {
     let current_depth = comments.get(current_index)?.depth;
     if current_depth == 0 {
         return None;
@@ -79,26 +79,53 @@ fn nearest_parent_index(comments: &[Comment], current_index: usize) -> Option<us
         .find(|&index| comments[index].depth < current_depth)
 }
 
+/// Indices of the comment's ancestors, from the top-level comment down to
+/// its parent.
+pub fn ancestor_indices(comments: &[Comment], current_index: usize) -> Vec<usize> {
+    let mut ancestors = Vec::new();
+    let mut index = current_index;
+    while let Some(parent_index) = nearest_parent_index(comments, index) {
+        ancestors.push(parent_index);
+        index = parent_index;
+    }
+    ancestors.reverse();
+    ancestors
+}
+
+pub fn top_level_ancestor_index(comments: &[Comment], current_index: usize) -> Option<usize> {
+    ancestor_indices(comments, current_index).first().copied()
+}
+
+pub fn next_top_level_index(comments: &[Comment], current_index: usize) -> Option<usize> {
+    comments
+        .iter()
+        .enumerate()
+        .skip(current_index + 1)
+        .find(|(_, comment)| comment.depth == 0)
+        .map(|(index, _)| index)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     fn comment(depth: usize) -> Comment {
+        authored_comment(depth, "a")
+    }
+
+    fn authored_comment(depth: usize, author: &str) -> Comment {
         Comment {
-            author: "a".to_string(),
-            text: "t".to_string(),
+            id: 0,
+            author: author.to_string(),
+            body: Vec::new(),
             published_at: 0,
             depth,
             ancestor_has_next_sibling: Vec::new(),
             is_last_sibling: true,
+            muted: false,
         }
     }
 
-    #[test]
-    fn current_index_clamps_to_comment_len() {
-        assert_eq!(current_index_from_scroll(&[0, 2, 4], 2, 10), Some(1));
-    }
-
     #[test]
     fn sibling_navigation_prefers_same_depth_then_outer() {
         let comments = vec![comment(0), comment(1), comment(2), comment(1), comment(0)];
@@ -106,10 +133,43 @@ mod tests {
         assert_eq!(next_sibling_or_outer_index(&comments, 3), Some(4));
     }
 
+    #[test]
+    fn sibling_navigation_skips_replies_and_stops_at_the_end() {
+        let comments = vec![comment(1), comment(2), comment(3), comment(1)];
+        assert_eq!(next_sibling_or_outer_index(&comments, 0), Some(3));
+        assert_eq!(next_sibling_or_outer_index(&comments, 1), Some(3));
+        assert_eq!(next_sibling_or_outer_index(&comments, 3), None);
+        assert_eq!(next_sibling_or_outer_index(&comments, 9), None);
+    }
+
     #[test]
     fn previous_navigation_finds_sibling_or_parent() {
         let comments = vec![comment(0), comment(1), comment(2), comment(1), comment(0)];
         assert_eq!(previous_sibling_or_parent_index(&comments, 3), Some(1));
         assert_eq!(previous_sibling_or_parent_index(&comments, 2), Some(1));
     }
+
+    #[test]
+    fn ancestors_lead_back_to_the_top_level_comment() {
+        let comments = vec![comment(0), comment(1), comment(2), comment(1), comment(0)];
+        assert_eq!(nearest_parent_index(&comments, 3), Some(0));
+        assert_eq!(ancestor_indices(&comments, 2), vec![0, 1]);
+        assert_eq!(ancestor_indices(&comments, 4), Vec::<usize>::new());
+        assert_eq!(top_level_ancestor_index(&comments, 2), Some(0));
+        assert_eq!(top_level_ancestor_index(&comments, 0), None);
+        assert_eq!(next_top_level_index(&comments, 2), Some(4));
+        assert_eq!(next_top_level_index(&comments, 4), None);
+    }
+
+    #[test]
+    fn next_author_index_skips_other_authors() {
+        let comments = vec![
+            authored_comment(0, "op"),
+            authored_comment(1, "b"),
+            authored_comment(2, "op"),
+        ];
+        assert_eq!(next_author_index(&comments, None, "op"), Some(0));
+        assert_eq!(next_author_index(&comments, Some(0), "op"), Some(2));
+        assert_eq!(next_author_index(&comments, Some(2), "op"), None);
+    }
 }
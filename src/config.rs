@@ -0,0 +1,85 @@
+use crate::watch::WatchRule;
+use serde::Deserialize;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// User settings read from `<config dir>/lazynews/config.toml`. Every field is
+/// optional, so a missing file behaves like an empty one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Fallback command that receives copied text on stdin, e.g. `pbcopy` or
+    /// `wl-copy`. It is used when OSC 52 is unavailable: in terminals known
+    /// to ignore it, or when the sequence cannot be written.
+    pub clipboard_command: Option<String>,
+    /// Command used to open URLs, with `{url}` standing in for the URL, e.g.
+    /// `firefox --new-tab {url}`. Falls back to `$BROWSER`, then the system
+    /// default browser. The command is launched detached from the terminal.
+    pub opener: Option<String>,
+    /// Command whose output the reader pane shows instead of its own article
+    /// extraction, e.g. `lynx -dump {url}`.
+    pub reader_command: Option<String>,
+    /// `[[watch]]` rules that raise an alert when a matching story shows up in
+    /// any feed.
+    pub watch: Vec<WatchRule>,
+    /// Extra attempts for HN requests that time out or get a 5xx or 429
+    /// response. Defaults to 3; `0` disables retries.
+    pub retries: Option<u32>,
+    /// Base URL of the HN API, for pointing lazynews at a mirror or proxy.
+    /// Defaults to `https://hacker-news.firebaseio.com/v0`.
+    pub api_url: Option<String>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents)
+                .map_err(|error| format!("Invalid config {}: {error}", path.display())),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(format!("Failed to read {}: {error}", path.display())),
+        }
+    }
+
+    fn parse(contents: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(contents)
+    }
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("lazynews"))
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(CONFIG_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_optional_settings() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+
+        let config = Config::parse("clipboard_command = \"wl-copy\"").unwrap();
+        assert_eq!(config.clipboard_command.as_deref(), Some("wl-copy"));
+        assert_eq!(config.opener, None);
+        assert_eq!(config.reader_command, None);
+        assert_eq!(config.retries, None);
+        assert_eq!(Config::parse("retries = 0").unwrap().retries, Some(0));
+        assert_eq!(config.api_url, None);
+
+        let config = Config::parse(
+            "[[watch]]\nkeyword = \"rust\"\n\n[[watch]]\ndomain = \"github.com\"\nmin_points = 50",
+        )
+        .unwrap();
+        assert_eq!(config.watch.len(), 2);
+        assert_eq!(config.watch[1].min_points, Some(50));
+
+        assert!(Config::parse("clipboard_command = 3").is_err());
+    }
+}
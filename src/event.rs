@@ -1,4 +1,7 @@
-use crate::hn::{Comment, Item};
+use crate::{
+    hn::{Comment, CommentsPage, FetchError, Item, UserProfile},
+    reader::Article,
+};
 use color_eyre::eyre::OptionExt;
 use crossterm::event::{Event as CrosstermEvent, EventStream, KeyEvent, KeyEventKind};
 use futures::StreamExt;
@@ -11,6 +14,8 @@ const TICK_FPS: f64 = 8.0;
 pub enum PostsFetchMode {
     Replace,
     Append,
+    /// Stories that failed to load earlier, appended once they load.
+    RetryFailed,
 }
 
 #[derive(Debug)]
@@ -18,6 +23,8 @@ pub struct PostsFetchResult {
     pub mode: PostsFetchMode,
     pub story_ids: Option<Vec<u64>>,
     pub items: Vec<Item>,
+    /// IDs of the page's items that could not be loaded, even after retries.
+    pub failed: Vec<u64>,
     pub next_story_index: usize,
 }
 
@@ -26,14 +33,45 @@ pub enum AppEvent {
     Refresh,
     PostsFetched {
         request_id: u64,
-        result: Result<PostsFetchResult, String>,
+        result: Result<PostsFetchResult, FetchError>,
     },
     LoadCommentsComplete {
         post_id: u64,
-        result: Result<Vec<Comment>, String>,
+        result: Result<Box<CommentsPage>, FetchError>,
+    },
+    CommentsProgress {
+        post_id: u64,
+        comments: Vec<Comment>,
+    },
+    MoreCommentsLoaded {
+        post_id: u64,
+        page: Box<CommentsPage>,
+    },
+    ItemFetched {
+        item_id: u64,
+        result: Result<Box<Item>, FetchError>,
+    },
+    UserProfileFetched {
+        username: String,
+        result: Result<Option<Box<UserProfile>>, FetchError>,
+    },
+    ArticleFetched {
+        url: String,
+        result: Result<Box<Article>, String>,
     },
     Quit,
     OpenPost(String),
+    OpenFinished {
+        url: String,
+        result: Result<(), String>,
+    },
+    CopyFinished {
+        what: String,
+        result: Result<(), String>,
+    },
+    WatchScanFinished {
+        result: Result<Vec<Item>, FetchError>,
+    },
 }
 
 #[derive(Debug)]
@@ -43,6 +81,17 @@ pub enum Event {
     App(AppEvent),
 }
 
+#[derive(Clone)]
+pub struct AppEventSender {
+    sender: UnboundedSender<Event>,
+}
+
+impl AppEventSender {
+    pub fn send(&self, app_event: AppEvent) {
+        let _ = self.sender.send(Event::App(app_event));
+    }
+}
+
 pub struct EventHandler {
     sender: UnboundedSender<Event>,
     receiver: UnboundedReceiver<Event>,
@@ -66,6 +115,12 @@ impl EventHandler {
         let _ = self.sender.send(Event::App(app_event));
     }
 
+    pub fn app_sender(&self) -> AppEventSender {
+        AppEventSender {
+            sender: self.sender.clone(),
+        }
+    }
+
     pub fn send_async<F>(&self, task: F)
     where
         F: Future<Output = AppEvent> + Send + 'static,
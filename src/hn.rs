@@ -1,20 +1,113 @@
+use crate::{
+    html::{Block, blocks_to_plain_text, parse_hn_html},
+    mute::MuteFilter,
+};
 use futures::{StreamExt, stream};
-use reqwest::Error;
-use serde::Deserialize;
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, de::DeserializeOwned};
 use std::{
-    collections::{HashMap, HashSet},
+    cmp::Reverse,
+    collections::{HashMap, HashSet, hash_map::RandomState},
+    fmt,
+    hash::{BuildHasher, Hasher},
     result::Result,
     time::Duration,
 };
 
 const HN_API_BASE: &str = "https://hacker-news.firebaseio.com/v0";
-const ITEM_URL_BASE: &str = "https://hacker-news.firebaseio.com/v0/item";
 const HN_DISCUSSION_URL_BASE: &str = "https://news.ycombinator.com/item?id=";
 const DEFAULT_CONCURRENCY: usize = 20;
 const DEFAULT_TIMEOUT_SECS: u64 = 10;
+/// Extra attempts for requests that time out or hit a 5xx or 429 response.
+pub const DEFAULT_RETRIES: u32 = 3;
+const RETRY_BASE_DELAY_MS: u64 = 250;
+const RETRY_MAX_DELAY_MS: u64 = 4_000;
+/// Deepest comment chain followed when resolving a comment to its story.
+const MAX_PARENT_DEPTH: usize = 32;
 const USER_AGENT: &str = "lazynews/0.1";
 
-#[derive(Debug, Deserialize)]
+/// Why a request to the Hacker News API failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// The API could not be reached at all, usually because there is no
+    /// network connection.
+    Offline,
+    Timeout,
+    /// The connection failed part way through the request.
+    Network(String),
+    Status(StatusCode),
+    /// The response was not the JSON that was expected.
+    Decode(String),
+    /// A newer request replaced this one before it finished.
+    Cancelled,
+}
+
+impl FetchError {
+    /// Timeouts, server errors and rate limiting are worth another attempt.
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::Timeout => true,
+            Self::Status(status) => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            _ => false,
+        }
+    }
+
+    /// What the user can do about the failure, if trying again could help.
+    pub fn retry_hint(&self) -> Option<&'static str> {
+        match self {
+            Self::Offline => Some("check your connection and try again"),
+            Self::Status(status) if *status == StatusCode::TOO_MANY_REQUESTS => {
+                Some("wait a moment and try again")
+            }
+            Self::Timeout | Self::Network(_) => Some("try again"),
+            Self::Status(status) if status.is_server_error() => Some("try again"),
+            Self::Status(_) | Self::Decode(_) | Self::Cancelled => None,
+        }
+    }
+
+    /// The error followed by its retry hint, for showing to the user.
+    pub fn describe(&self) -> String {
+        match self.retry_hint() {
+            Some(hint) => format!("{self}, {hint}"),
+            None => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Offline => write!(f, "could not reach Hacker News"),
+            Self::Timeout => write!(f, "the request timed out"),
+            Self::Network(error) => write!(f, "network error: {error}"),
+            Self::Status(status) => write!(f, "Hacker News responded with {status}"),
+            Self::Decode(error) => write!(f, "unexpected response: {error}"),
+            Self::Cancelled => write!(f, "the request was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<reqwest::Error> for FetchError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            Self::Timeout
+        } else if let Some(status) = error.status() {
+            Self::Status(status)
+        } else if error.is_decode() {
+            Self::Decode(error.to_string())
+        } else if error.is_connect() {
+            Self::Offline
+        } else {
+            Self::Network(error.to_string())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
 pub struct Item {
     pub id: u64,
     pub title: Option<String>,
@@ -25,6 +118,9 @@ pub struct Item {
     pub time: Option<u64>,
     pub text: Option<String>,
     pub kids: Option<Vec<u64>>,
+    pub parent: Option<u64>,
+    /// Option IDs of a poll.
+    pub parts: Option<Vec<u64>>,
     #[serde(rename = "type")]
     pub kind: Option<String>,
     #[serde(default)]
@@ -33,19 +129,141 @@ pub struct Item {
     pub deleted: bool,
 }
 
+/// Items fetched by ID, in request order, and the IDs that could not be loaded.
+#[derive(Debug, Clone, Default)]
+pub struct FetchedItems {
+    pub items: Vec<Item>,
+    pub failed: Vec<u64>,
+}
+
+/// The Following feed's story IDs and the activity items that could not be
+/// loaded while building it.
+#[derive(Debug, Clone, Default)]
+pub struct FollowingStoryIds {
+    pub story_ids: Vec<u64>,
+    pub failed: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct User {
+    pub id: String,
+    #[serde(default)]
+    pub karma: i64,
+    #[serde(default)]
+    pub created: u64,
+    pub about: Option<String>,
+    #[serde(default)]
+    pub submitted: Vec<u64>,
+}
+
+/// A user together with their most recent live submissions, newest first.
+#[derive(Debug, Clone)]
+pub struct UserProfile {
+    pub user: User,
+    pub submissions: Vec<Item>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Comment {
+    pub id: u64,
     pub author: String,
-    pub text: String,
+    pub body: Vec<Block>,
     pub published_at: u64,
     pub depth: usize,
     pub ancestor_has_next_sibling: Vec<bool>,
     pub is_last_sibling: bool,
+    /// Written by a muted author; rendered as a placeholder until revealed.
+    pub muted: bool,
+}
+
+/// One answer of a poll and the votes it received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PollOption {
+    pub text: String,
+    pub score: u64,
+}
+
+/// Traversal state of a post's comment tree. It is handed back with every page so
+/// that further pages resume from the pending queue instead of the root.
+#[derive(Debug, Clone, Default)]
+pub struct CommentThread {
+    root_kids: Vec<u64>,
+    total: u64,
+    pending: Vec<u64>,
+    scheduled_ids: HashSet<u64>,
+    failed_ids: HashSet<u64>,
+    items_by_id: HashMap<u64, Item>,
+    has_more: bool,
+    page_limit: usize,
+    poll_options: Vec<PollOption>,
+}
+
+impl CommentThread {
+    fn new(root_kids: Vec<u64>, total: u64) -> Self {
+        Self {
+            pending: root_kids.iter().rev().copied().collect(),
+            scheduled_ids: root_kids.iter().copied().collect(),
+            root_kids,
+            total,
+            ..Self::default()
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn has_more(&self) -> bool {
+        self.has_more
+    }
+
+    /// Lays out the fetched comments in `order`. Ranked order keeps the loaded
+    /// page; the other orders use every comment fetched so far.
+    pub fn comments(&self, order: CommentOrder, mutes: &MuteFilter) -> Vec<Comment> {
+        let limit = match order {
+            CommentOrder::Ranked => self.page_limit,
+            _ => usize::MAX,
+        };
+        build_comment_prefix(
+            &self.root_kids,
+            limit,
+            &self.items_by_id,
+            &self.failed_ids,
+            mutes,
+            order,
+        )
+        .0
+    }
+
+    /// Comments that could not be loaded, even after retries.
+    pub fn failed_count(&self) -> usize {
+        self.failed_ids.len()
+    }
+
+    /// Queues failed comments to be fetched again, returning how many.
+    pub fn retry_failed(&mut self) -> usize {
+        let failed: Vec<u64> = self.failed_ids.drain().collect();
+        self.pending.extend(&failed);
+        failed.len()
+    }
+
+    /// The options of a poll, in ballot order; empty for other posts.
+    pub fn poll_options(&self) -> &[PollOption] {
+        &self.poll_options
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CommentsPage {
+    pub comments: Vec<Comment>,
+    pub thread: CommentThread,
 }
 
 #[derive(Clone)]
 pub struct HackerNewsApi {
     client: reqwest::Client,
+    base_url: String,
+    retries: u32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -59,6 +277,15 @@ pub enum StoryFeed {
 }
 
 impl StoryFeed {
+    pub const ALL: [Self; 6] = [
+        Self::Top,
+        Self::New,
+        Self::Ask,
+        Self::Show,
+        Self::Jobs,
+        Self::Best,
+    ];
+
     fn endpoint(self) -> &'static str {
         match self {
             Self::Top => "topstories",
@@ -71,6 +298,71 @@ impl StoryFeed {
     }
 }
 
+/// How the comments pane arranges a thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommentOrder {
+    /// HN's own ranking, as listed in `kids`.
+    #[default]
+    Ranked,
+    Oldest,
+    Newest,
+    /// Siblings with the most direct replies first.
+    Replies,
+    /// Every comment oldest first, without the reply tree.
+    Flat,
+}
+
+impl CommentOrder {
+    pub const ALL: [Self; 5] = [
+        Self::Ranked,
+        Self::Oldest,
+        Self::Newest,
+        Self::Replies,
+        Self::Flat,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ranked => "ranked",
+            Self::Oldest => "oldest first",
+            Self::Newest => "newest first",
+            Self::Replies => "most replies",
+            Self::Flat => "flat",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|order| *order == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// `ids` rearranged for this order. Unfetched items go last.
+    fn arrange(self, ids: &[u64], items_by_id: &HashMap<u64, Item>) -> Vec<u64> {
+        let mut ids = ids.to_vec();
+        match self {
+            Self::Ranked | Self::Flat => {}
+            Self::Oldest => ids.sort_by_key(|id| {
+                let item = items_by_id.get(id);
+                (item.is_none(), item.and_then(|item| item.time))
+            }),
+            Self::Newest => {
+                ids.sort_by_key(|id| Reverse(items_by_id.get(id).map(|item| item.time)))
+            }
+            Self::Replies => ids.sort_by_key(|id| {
+                Reverse(
+                    items_by_id
+                        .get(id)
+                        .map(|item| item.kids.as_ref().map_or(0, Vec::len)),
+                )
+            }),
+        }
+        ids
+    }
+}
+
 impl HackerNewsApi {
     pub fn new() -> Self {
         let client = reqwest::Client::builder()
@@ -79,100 +371,325 @@ impl HackerNewsApi {
             .build()
             .unwrap_or_else(|_| reqwest::Client::new());
 
-        Self { client }
+        Self {
+            client,
+            base_url: HN_API_BASE.to_string(),
+            retries: DEFAULT_RETRIES,
+        }
+    }
+
+    /// Points the client at another server speaking the HN API, such as a mirror.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// GETs `url` as JSON, retrying transient failures with jittered
+    /// exponential backoff.
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, FetchError> {
+        let mut attempt = 0;
+        loop {
+            match self.try_get_json(url).await {
+                Err(error) if attempt < self.retries && error.is_transient() => {
+                    tokio::time::sleep(backoff_delay(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
     }
 
-    async fn fetch_single_item(&self, id: u64) -> Result<Item, Error> {
-        let item_url = format!("{ITEM_URL_BASE}/{id}.json");
-        self.client
-            .get(item_url)
+    async fn try_get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, FetchError> {
+        Ok(self
+            .client
+            .get(url)
             .send()
             .await?
             .error_for_status()?
-            .json::<Item>()
+            .json::<T>()
+            .await?)
+    }
+
+    pub async fn fetch_single_item(&self, id: u64) -> Result<Item, FetchError> {
+        self.get_json(&format!("{}/item/{id}.json", self.base_url))
             .await
     }
 
-    pub async fn fetch_story_ids(&self, feed: StoryFeed) -> Result<Vec<u64>, Error> {
-        let feed_url = format!("{HN_API_BASE}/{}.json", feed.endpoint());
-        self.client
-            .get(feed_url)
-            .send()
-            .await?
-            .error_for_status()?
-            .json::<Vec<u64>>()
+    pub async fn fetch_story_ids(&self, feed: StoryFeed) -> Result<Vec<u64>, FetchError> {
+        self.get_json(&format!("{}/{}.json", self.base_url, feed.endpoint()))
             .await
     }
 
-    pub async fn fetch_items_by_ids(
+    /// Returns `None` when HN has no user by that name.
+    /// Fetches a user, treating names HN could not have issued as unknown so
+    /// they never reach the URL path.
+    pub async fn fetch_user(&self, username: &str) -> Result<Option<User>, FetchError> {
+        let is_hn_username = !username.is_empty()
+            && username
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_'));
+        if !is_hn_username {
+            return Ok(None);
+        }
+        self.get_json(&format!("{}/user/{username}.json", self.base_url))
+            .await
+    }
+
+    pub async fn fetch_user_profile(
         &self,
-        ids: &[u64],
-        _feed: StoryFeed,
-    ) -> Result<Vec<Item>, Error> {
-        if ids.is_empty() {
-            return Ok(Vec::new());
+        username: &str,
+        submission_limit: usize,
+    ) -> Result<Option<UserProfile>, FetchError> {
+        let Some(user) = self.fetch_user(username).await? else {
+            return Ok(None);
+        };
+
+        let recent_ids: Vec<u64> = user
+            .submitted
+            .iter()
+            .take(submission_limit)
+            .copied()
+            .collect();
+        let submissions = self
+            .fetch_items_in_order(&recent_ids)
+            .await
+            .items
+            .into_iter()
+            .filter(|item| !item.dead && !item.deleted)
+            .collect();
+
+        Ok(Some(UserProfile { user, submissions }))
+    }
+
+    /// Fetches items concurrently, keeping the order of `ids` and collecting the
+    /// ones that still fail after retries.
+    async fn fetch_items_in_order(&self, ids: &[u64]) -> FetchedItems {
+        let mut results: Vec<(usize, u64, Option<Item>)> =
+            stream::iter(ids.iter().copied().enumerate())
+                .map(|(idx, id)| async move { (idx, id, self.fetch_single_item(id).await.ok()) })
+                .buffer_unordered(DEFAULT_CONCURRENCY)
+                .collect()
+                .await;
+        results.sort_by_key(|(idx, _, _)| *idx);
+
+        let mut fetched = FetchedItems::default();
+        for (_, id, item) in results {
+            match item {
+                Some(item) => fetched.items.push(item),
+                None => fetched.failed.push(id),
+            }
         }
+        fetched
+    }
 
-        let mut indexed: Vec<(usize, Item)> = stream::iter(ids.iter().copied().enumerate())
-            .map(|(idx, id)| async move {
-                self.fetch_single_item(id)
-                    .await
-                    .map(|item| (idx, item))
-                    .ok()
-            })
+    /// Builds the story list for followed users: the stories they submitted and
+    /// the stories they commented on, most recent activity first. `failed` holds
+    /// the submissions and parent comments that could not be loaded.
+    pub async fn fetch_following_story_ids(
+        &self,
+        usernames: &[String],
+        submissions_per_user: usize,
+    ) -> Result<FollowingStoryIds, FetchError> {
+        let users: Vec<User> = stream::iter(usernames.iter().cloned())
+            .map(|username| async move { self.fetch_user(&username).await })
             .buffer_unordered(DEFAULT_CONCURRENCY)
-            .filter_map(|item| async move { item })
-            .collect()
-            .await;
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        let submission_ids: Vec<u64> = users
+            .iter()
+            .flat_map(|user| user.submitted.iter().take(submissions_per_user).copied())
+            .collect();
+        let mut activity: Vec<(u64, u64)> = Vec::new();
+        let mut pending: Vec<(u64, Item)> = Vec::new();
+        let FetchedItems { items, mut failed } = self.fetch_items_in_order(&submission_ids).await;
+        for item in items {
+            if item.dead || item.deleted {
+                continue;
+            }
+            let time = item.time.unwrap_or_default();
+            if item.kind.as_deref() == Some("comment") {
+                pending.push((time, item));
+            } else {
+                activity.push((time, item.id));
+            }
+        }
 
-        indexed.sort_by_key(|(idx, _)| *idx);
+        // Walk comments up one level per round until each reaches its story.
+        for _ in 0..MAX_PARENT_DEPTH {
+            if pending.is_empty() {
+                break;
+            }
+            let mut parent_ids: Vec<u64> =
+                pending.iter().filter_map(|(_, item)| item.parent).collect();
+            parent_ids.sort_unstable();
+            parent_ids.dedup();
+            let fetched = self.fetch_items_in_order(&parent_ids).await;
+            failed.extend(fetched.failed);
+            let parents: HashMap<u64, Item> = fetched
+                .items
+                .into_iter()
+                .map(|item| (item.id, item))
+                .collect();
+
+            pending = pending
+                .into_iter()
+                .filter_map(|(time, item)| {
+                    let parent = parents.get(&item.parent?)?.clone();
+                    if parent.kind.as_deref() == Some("comment") {
+                        Some((time, parent))
+                    } else {
+                        activity.push((time, parent.id));
+                        None
+                    }
+                })
+                .collect();
+        }
 
-        Ok(indexed
+        activity.sort_by_key(|(time, _)| Reverse(*time));
+        let mut seen = HashSet::new();
+        let story_ids = activity
             .into_iter()
-            .map(|(_, mut item)| {
+            .filter_map(|(_, id)| seen.insert(id).then_some(id))
+            .collect();
+        Ok(FollowingStoryIds { story_ids, failed })
+    }
+
+    /// Fetches the first `per_feed` stories of every feed, each story once.
+    pub async fn fetch_feed_heads(&self, per_feed: usize) -> Result<FetchedItems, FetchError> {
+        let mut ids: Vec<u64> = Vec::new();
+        let mut seen = HashSet::new();
+        for feed in StoryFeed::ALL {
+            let story_ids = self.fetch_story_ids(feed).await?;
+            ids.extend(
+                story_ids
+                    .into_iter()
+                    .take(per_feed)
+                    .filter(|id| seen.insert(*id)),
+            );
+        }
+        self.fetch_items_by_ids(&ids).await
+    }
+
+    pub async fn fetch_items_by_ids(&self, ids: &[u64]) -> Result<FetchedItems, FetchError> {
+        if ids.is_empty() {
+            return Ok(FetchedItems::default());
+        }
+
+        let FetchedItems { items, failed } = self.fetch_items_in_order(ids).await;
+        let items = items
+            .into_iter()
+            .map(|mut item| {
                 if item.url.is_none() {
-                    item.url = Some(format!("{HN_DISCUSSION_URL_BASE}{}", item.id));
+                    item.url = Some(discussion_url(item.id));
                 }
                 item
             })
             .filter(|item| {
-                let is_supported = matches!(item.kind.as_deref(), Some("story" | "job"));
+                let is_supported = matches!(item.kind.as_deref(), Some("story" | "job" | "poll"));
                 !item.dead && !item.deleted && is_supported
             })
-            .collect())
+            .collect();
+        Ok(FetchedItems { items, failed })
     }
 
-    pub async fn fetch_comments(&self, post_id: u64, limit: usize) -> Result<Vec<Comment>, Error> {
-        if limit == 0 {
-            return Ok(Vec::new());
-        }
-
+    pub async fn fetch_comments(
+        &self,
+        post_id: u64,
+        limit: usize,
+        mutes: &MuteFilter,
+        on_progress: impl FnMut(Vec<Comment>),
+    ) -> Result<CommentsPage, FetchError> {
         let post = self.fetch_single_item(post_id).await?;
-        let root_kids = post.kids.unwrap_or_default();
-        if root_kids.is_empty() {
-            return Ok(Vec::new());
+        let mut thread = CommentThread::new(
+            post.kids.unwrap_or_default(),
+            post.descendants.unwrap_or_default(),
+        );
+        if let Some(parts) = post.parts.as_deref() {
+            thread.poll_options = self.fetch_poll_options(parts).await;
         }
 
-        let mut pending: Vec<u64> = root_kids.iter().rev().copied().collect();
-        let mut scheduled_ids: HashSet<u64> = root_kids.iter().copied().collect();
-        let mut failed_ids: HashSet<u64> = HashSet::new();
-        let mut items_by_id: HashMap<u64, Item> = HashMap::new();
+        Ok(self
+            .fetch_more_comments(thread, limit, mutes, on_progress)
+            .await)
+    }
+
+    async fn fetch_poll_options(&self, parts: &[u64]) -> Vec<PollOption> {
+        self.fetch_items_in_order(parts)
+            .await
+            .items
+            .into_iter()
+            .filter(|item| item.kind.as_deref() == Some("pollopt") && !item.dead && !item.deleted)
+            .map(|item| PollOption {
+                text: item
+                    .text
+                    .as_deref()
+                    .map(|text| blocks_to_plain_text(&parse_hn_html(text)))
+                    .unwrap_or_default(),
+                score: item.score.unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Fetches comments until `limit` are resolved, calling `on_progress` with the
+    /// complete prefix whenever it grows before the page is finished.
+    pub async fn fetch_more_comments(
+        &self,
+        mut thread: CommentThread,
+        limit: usize,
+        mutes: &MuteFilter,
+        mut on_progress: impl FnMut(Vec<Comment>),
+    ) -> CommentsPage {
+        thread.page_limit = limit;
+        if limit == 0 || thread.root_kids.is_empty() {
+            thread.has_more = false;
+            return CommentsPage {
+                comments: Vec::new(),
+                thread,
+            };
+        }
 
+        // Build one comment past the limit so we know whether another page exists.
+        let probe_limit = limit + 1;
+        let mut streamed_len = 0usize;
         loop {
-            if let Some(comments) =
-                build_comments_from_cache(&root_kids, limit, &items_by_id, &failed_ids)
-                && (comments.len() >= limit || pending.is_empty())
-            {
-                return Ok(comments);
+            let (mut comments, complete) = build_comment_prefix(
+                &thread.root_kids,
+                probe_limit,
+                &thread.items_by_id,
+                &thread.failed_ids,
+                mutes,
+                CommentOrder::Ranked,
+            );
+            if complete && (comments.len() >= probe_limit || thread.pending.is_empty()) {
+                thread.has_more = comments.len() > limit;
+                comments.truncate(limit);
+                return CommentsPage { comments, thread };
             }
 
-            if pending.is_empty() {
+            if thread.pending.is_empty() {
                 break;
             }
 
+            comments.truncate(limit);
+            if comments.len() > streamed_len {
+                streamed_len = comments.len();
+                on_progress(comments);
+            }
+
             let mut batch: Vec<u64> = Vec::with_capacity(DEFAULT_CONCURRENCY);
             while batch.len() < DEFAULT_CONCURRENCY {
-                match pending.pop() {
+                match thread.pending.pop() {
                     Some(id) => batch.push(id),
                     None => break,
                 }
@@ -194,26 +711,72 @@ impl HackerNewsApi {
                 match maybe_item {
                     Some(item) => {
                         for kid in item.kids.as_deref().unwrap_or(&[]).iter().rev() {
-                            if scheduled_ids.insert(*kid) {
-                                pending.push(*kid);
+                            if thread.scheduled_ids.insert(*kid) {
+                                thread.pending.push(*kid);
                             }
                         }
-                        items_by_id.insert(id, item);
+                        thread.items_by_id.insert(id, item);
                     }
                     None => {
-                        failed_ids.insert(id);
+                        thread.failed_ids.insert(id);
                     }
                 }
             }
         }
 
-        Ok(
-            build_comments_from_cache(&root_kids, limit, &items_by_id, &failed_ids)
-                .unwrap_or_default(),
+        let mut comments = build_comments_from_cache(
+            &thread.root_kids,
+            probe_limit,
+            &thread.items_by_id,
+            &thread.failed_ids,
+            mutes,
+            CommentOrder::Ranked,
         )
+        .unwrap_or_default();
+        thread.has_more = comments.len() > limit;
+        comments.truncate(limit);
+        CommentsPage { comments, thread }
     }
 }
 
+/// Exponential backoff with jitter, so concurrent retries spread out: a delay
+/// between half and all of `base * 2^attempt`, capped.
+fn backoff_delay(attempt: u32) -> Duration {
+    let ceiling = RETRY_BASE_DELAY_MS
+        .saturating_mul(1 << attempt.min(16))
+        .min(RETRY_MAX_DELAY_MS);
+    let jitter = RandomState::new().build_hasher().finish() % (ceiling / 2 + 1);
+    Duration::from_millis(ceiling / 2 + jitter)
+}
+
+pub fn discussion_url(item_id: u64) -> String {
+    format!("{HN_DISCUSSION_URL_BASE}{item_id}")
+}
+
+/// The host of `url` without a leading `www.`, lowercased.
+pub fn url_domain(url: &str) -> Option<String> {
+    let host = Url::parse(url).ok()?.host_str()?.to_ascii_lowercase();
+    Some(
+        host.strip_prefix("www.")
+            .map(str::to_string)
+            .unwrap_or(host),
+    )
+}
+
+/// Extracts an item ID from a bare ID or a `news.ycombinator.com/item?id=` URL.
+pub fn parse_item_reference(input: &str) -> Option<u64> {
+    let input = input.trim();
+    if let Ok(id) = input.parse::<u64>() {
+        return Some(id);
+    }
+
+    let (_, query) = input.split_once("news.ycombinator.com/item?")?;
+    query
+        .split(['&', '#'])
+        .find_map(|pair| pair.strip_prefix("id="))
+        .and_then(|id| id.parse::<u64>().ok())
+}
+
 struct PendingComment {
     id: u64,
     depth: usize,
@@ -226,7 +789,46 @@ fn build_comments_from_cache(
     limit: usize,
     items_by_id: &HashMap<u64, Item>,
     failed_ids: &HashSet<u64>,
+    mutes: &MuteFilter,
+    order: CommentOrder,
 ) -> Option<Vec<Comment>> {
+    let (comments, complete) =
+        build_comment_prefix(root_kids, limit, items_by_id, failed_ids, mutes, order);
+    complete.then_some(comments)
+}
+
+/// Builds comments in `order` until `limit` is reached. Ranked order stops at
+/// the first item that has not been fetched yet, so the flag reports whether
+/// the traversal finished without hitting one; the other orders can only
+/// arrange what has been fetched and skip missing items instead.
+fn build_comment_prefix(
+    root_kids: &[u64],
+    limit: usize,
+    items_by_id: &HashMap<u64, Item>,
+    failed_ids: &HashSet<u64>,
+    mutes: &MuteFilter,
+    order: CommentOrder,
+) -> (Vec<Comment>, bool) {
+    if order == CommentOrder::Flat {
+        let (mut comments, _) = build_comment_prefix(
+            root_kids,
+            usize::MAX,
+            items_by_id,
+            failed_ids,
+            mutes,
+            CommentOrder::Oldest,
+        );
+        comments.sort_by_key(|comment| comment.published_at);
+        comments.truncate(limit);
+        for comment in &mut comments {
+            comment.depth = 0;
+            comment.ancestor_has_next_sibling.clear();
+            comment.is_last_sibling = true;
+        }
+        return (comments, true);
+    }
+
+    let root_kids = order.arrange(root_kids, items_by_id);
     let root_count = root_kids.len();
     let mut stack: Vec<PendingComment> = Vec::with_capacity(root_count);
     for (index, kid) in root_kids.iter().enumerate().rev() {
@@ -246,13 +848,13 @@ fn build_comments_from_cache(
         }
 
         let Some(item) = items_by_id.get(&node.id) else {
-            if failed_ids.contains(&node.id) {
+            if failed_ids.contains(&node.id) || order != CommentOrder::Ranked {
                 continue;
             }
-            return None;
+            return (comments, false);
         };
 
-        let child_ids = item.kids.as_deref().unwrap_or(&[]);
+        let child_ids = order.arrange(item.kids.as_deref().unwrap_or(&[]), items_by_id);
         let child_count = child_ids.len();
         let mut child_ancestor_has_next_sibling = node.ancestor_has_next_sibling.clone();
         child_ancestor_has_next_sibling.push(!node.is_last_sibling);
@@ -274,18 +876,21 @@ fn build_comments_from_cache(
             continue;
         }
 
-        let cleaned_text = clean_comment_text(item.text.as_deref().unwrap_or_default());
-        if cleaned_text.is_empty() {
+        let body = parse_hn_html(item.text.as_deref().unwrap_or_default());
+        if blocks_to_plain_text(&body).trim().is_empty() {
             continue;
         }
 
+        let author = item
+            .by
+            .clone()
+            .filter(|author| !author.is_empty())
+            .unwrap_or_else(|| "unknown".to_string());
         comments.push(Comment {
-            author: item
-                .by
-                .clone()
-                .filter(|author| !author.is_empty())
-                .unwrap_or_else(|| "unknown".to_string()),
-            text: cleaned_text,
+            id: item.id,
+            muted: mutes.is_author_muted(&author),
+            author,
+            body,
             published_at: item.time.unwrap_or_default(),
             depth: node.depth,
             ancestor_has_next_sibling: node.ancestor_has_next_sibling,
@@ -293,73 +898,23 @@ fn build_comments_from_cache(
         });
     }
 
-    Some(comments)
-}
-
-fn clean_comment_text(text: &str) -> String {
-    let paragraph_normalized = text
-        .replace("<p>", "\n")
-        .replace("</p>", "")
-        .replace("<br>", "\n")
-        .replace("<br/>", "\n")
-        .replace("<br />", "\n");
-
-    let without_tags = strip_html_tags(&paragraph_normalized);
-    let decoded = decode_html_entities(&without_tags);
-
-    let compacted = decoded
-        .lines()
-        .map(str::trim)
-        .scan(false, |last_blank, line| {
-            if line.is_empty() {
-                if *last_blank {
-                    return Some(None);
-                }
-                *last_blank = true;
-                return Some(Some(""));
-            }
-
-            *last_blank = false;
-            Some(Some(line))
-        })
-        .flatten()
-        .collect::<Vec<_>>()
-        .join("\n")
-        .trim()
-        .to_string();
-
-    compacted.trim().to_string()
-}
-
-fn strip_html_tags(text: &str) -> String {
-    let mut output = String::with_capacity(text.len());
-    let mut in_tag = false;
-
-    for ch in text.chars() {
-        match ch {
-            '<' => in_tag = true,
-            '>' => in_tag = false,
-            _ if !in_tag => output.push(ch),
-            _ => {}
-        }
-    }
-
-    output
-}
-
-fn decode_html_entities(text: &str) -> String {
-    text.replace("&quot;", "\"")
-        .replace("&#x27;", "'")
-        .replace("&#x2F;", "/")
-        .replace("&amp;", "&")
-        .replace("&lt;", "<")
-        .replace("&gt;", ">")
+    (comments, true)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::{HashMap, HashSet};
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
 
     fn base_item(id: u64) -> Item {
         Item {
@@ -372,6 +927,8 @@ mod tests {
             time: None,
             text: None,
             kids: None,
+            parent: None,
+            parts: None,
             kind: None,
             dead: false,
             deleted: false,
@@ -379,19 +936,18 @@ mod tests {
     }
 
     #[test]
-    fn clean_comment_text_normalizes_html_and_entities() {
-        let cleaned = clean_comment_text(
-            "<p>Hello &amp; <em>world</em></p><p>Line 2</p><br />&quot;quote&quot;",
+    fn parse_item_reference_accepts_ids_and_discussion_urls() {
+        assert_eq!(parse_item_reference(" 12345 "), Some(12345));
+        assert_eq!(
+            parse_item_reference(&format!("{HN_DISCUSSION_URL_BASE}42")),
+            Some(42)
         );
-
-        assert_eq!(cleaned, "Hello & world\nLine 2\n\"quote\"");
-    }
-
-    #[test]
-    fn clean_comment_text_collapses_extra_blank_lines() {
-        let cleaned = clean_comment_text("<p>One</p><p></p><p></p><p>Two</p>");
-
-        assert_eq!(cleaned, "One\n\nTwo");
+        assert_eq!(
+            parse_item_reference("news.ycombinator.com/item?foo=bar&id=7#comments"),
+            Some(7)
+        );
+        assert_eq!(parse_item_reference("https://example.com/item?id=1"), None);
+        assert_eq!(parse_item_reference("abc"), None);
     }
 
     #[test]
@@ -404,11 +960,43 @@ mod tests {
         root.kids = Some(vec![2]);
         items_by_id.insert(1, root);
 
-        let comments = build_comments_from_cache(&root_kids, 10, &items_by_id, &HashSet::new());
+        let comments = build_comments_from_cache(
+            &root_kids,
+            10,
+            &items_by_id,
+            &HashSet::new(),
+            &MuteFilter::default(),
+            CommentOrder::Ranked,
+        );
 
         assert!(comments.is_none());
     }
 
+    #[test]
+    fn build_comment_prefix_stops_at_first_missing_item() {
+        let root_kids = vec![1, 2, 3];
+        let mut items_by_id = HashMap::new();
+        for id in [1, 3] {
+            let mut item = base_item(id);
+            item.kind = Some("comment".to_string());
+            item.text = Some(format!("comment {id}"));
+            items_by_id.insert(id, item);
+        }
+
+        let (comments, complete) = build_comment_prefix(
+            &root_kids,
+            10,
+            &items_by_id,
+            &HashSet::new(),
+            &MuteFilter::default(),
+            CommentOrder::Ranked,
+        );
+
+        assert!(!complete);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(blocks_to_plain_text(&comments[0].body), "comment 1");
+    }
+
     #[test]
     fn build_comments_from_cache_skips_failed_and_filters_unsupported_items() {
         let root_kids = vec![10, 20, 30];
@@ -450,29 +1038,276 @@ mod tests {
         non_comment_root.text = Some("not a comment".to_string());
         items_by_id.insert(20, non_comment_root);
 
-        let comments =
-            build_comments_from_cache(&root_kids, 10, &items_by_id, &failed_ids).unwrap();
+        let comments = build_comments_from_cache(
+            &root_kids,
+            10,
+            &items_by_id,
+            &failed_ids,
+            &MuteFilter::default(),
+            CommentOrder::Ranked,
+        )
+        .unwrap();
 
         assert_eq!(comments.len(), 3);
         assert_eq!(comments[0].author, "alice");
-        assert_eq!(comments[0].text, "First\nline");
+        assert_eq!(blocks_to_plain_text(&comments[0].body), "First\nline");
         assert_eq!(comments[0].depth, 0);
         assert!(comments[0].ancestor_has_next_sibling.is_empty());
         assert!(!comments[0].is_last_sibling);
 
         assert_eq!(comments[1].author, "unknown");
-        assert_eq!(comments[1].text, "Parent two");
+        assert_eq!(blocks_to_plain_text(&comments[1].body), "Parent two");
         assert_eq!(comments[1].depth, 1);
         assert_eq!(comments[1].ancestor_has_next_sibling, vec![true]);
         assert!(comments[1].is_last_sibling);
 
         assert_eq!(comments[2].author, "carol");
-        assert_eq!(comments[2].text, "<tag> and 'quotes'");
+        assert_eq!(
+            blocks_to_plain_text(&comments[2].body),
+            "<tag> and 'quotes'"
+        );
         assert_eq!(comments[2].depth, 2);
         assert_eq!(comments[2].ancestor_has_next_sibling, vec![true, false]);
         assert!(comments[2].is_last_sibling);
     }
 
+    #[tokio::test]
+    async fn fetch_more_comments_resumes_from_cached_items() {
+        let mut thread = CommentThread::new(vec![1, 2], 2);
+        thread.pending.clear();
+        for id in [1, 2] {
+            let mut item = base_item(id);
+            item.kind = Some("comment".to_string());
+            item.text = Some(format!("comment {id}"));
+            thread.items_by_id.insert(id, item);
+        }
+
+        let api = HackerNewsApi::new();
+        let page = api
+            .fetch_more_comments(thread, 1, &MuteFilter::default(), |_| {})
+            .await;
+        assert_eq!(page.comments.len(), 1);
+        assert!(page.thread.has_more());
+        assert_eq!(page.thread.total(), 2);
+
+        let page = api
+            .fetch_more_comments(page.thread, 2, &MuteFilter::default(), |_| {})
+            .await;
+        assert_eq!(page.comments.len(), 2);
+        assert_eq!(blocks_to_plain_text(&page.comments[1].body), "comment 2");
+        assert!(!page.thread.has_more());
+    }
+
+    #[test]
+    fn comment_orders_rearrange_fetched_comments() {
+        let mut thread = CommentThread::new(vec![1, 2, 9], 6);
+        for (id, time, kids) in [
+            (1, 200, vec![3]),
+            (2, 100, vec![4, 5]),
+            (3, 300, vec![]),
+            (4, 150, vec![]),
+            (5, 50, vec![]),
+        ] {
+            let mut item = base_item(id);
+            item.kind = Some("comment".to_string());
+            item.text = Some(format!("comment {id}"));
+            item.time = Some(time);
+            item.kids = Some(kids);
+            thread.items_by_id.insert(id, item);
+        }
+        thread.page_limit = 10;
+        let ids = |order| {
+            thread
+                .comments(order, &MuteFilter::default())
+                .iter()
+                .map(|comment| comment.id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(CommentOrder::Ranked), vec![1, 3, 2, 4, 5]);
+        assert_eq!(ids(CommentOrder::Oldest), vec![2, 5, 4, 1, 3]);
+        assert_eq!(ids(CommentOrder::Newest), vec![1, 3, 2, 4, 5]);
+        assert_eq!(ids(CommentOrder::Replies), vec![2, 4, 5, 1, 3]);
+        assert_eq!(ids(CommentOrder::Flat), vec![5, 2, 4, 1, 3]);
+
+        let flat = thread.comments(CommentOrder::Flat, &MuteFilter::default());
+        assert!(flat.iter().all(|comment| comment.depth == 0));
+        let ranked = thread.comments(CommentOrder::Ranked, &MuteFilter::default());
+        assert_eq!(ranked[1].depth, 1);
+    }
+
+    #[test]
+    fn only_transient_errors_are_retried_and_hinted() {
+        let rate_limited = FetchError::Status(StatusCode::TOO_MANY_REQUESTS);
+        assert!(rate_limited.is_transient());
+        assert!(FetchError::Status(StatusCode::BAD_GATEWAY).is_transient());
+        assert!(FetchError::Timeout.is_transient());
+        assert!(!FetchError::Offline.is_transient());
+        assert!(!FetchError::Status(StatusCode::NOT_FOUND).is_transient());
+
+        assert_eq!(
+            rate_limited.describe(),
+            "Hacker News responded with 429 Too Many Requests, wait a moment and try again"
+        );
+        assert_eq!(FetchError::Decode("eof".to_string()).retry_hint(), None);
+        assert_eq!(FetchError::Cancelled.retry_hint(), None);
+    }
+
+    /// Answers each request with the next of `statuses`, repeating the last one,
+    /// and counts the requests. `200` responses carry a story.
+    async fn status_fixture(statuses: &'static [u16]) -> (HackerNewsApi, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 1024];
+                let _ = socket.read(&mut request).await;
+                let index = counter.fetch_add(1, Ordering::SeqCst);
+                let status = statuses[index.min(statuses.len() - 1)];
+                let body = if status == 200 {
+                    r#"{"id":7,"type":"story","title":"Fixture"}"#
+                } else {
+                    ""
+                };
+                let response = format!(
+                    "HTTP/1.1 {status} Fixture\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        let client = HackerNewsApi::new().with_base_url(format!("http://{address}"));
+        (client, requests)
+    }
+
+    #[tokio::test]
+    async fn server_errors_and_rate_limits_are_retried() {
+        let (client, requests) = status_fixture(&[503, 429, 200]).await;
+
+        let item = client.fetch_single_item(7).await.unwrap();
+
+        assert_eq!(item.title.as_deref(), Some("Fixture"));
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn retries_give_up_after_the_configured_attempts() {
+        let (client, requests) = status_fixture(&[500]).await;
+
+        let error = client
+            .with_retries(1)
+            .fetch_single_item(7)
+            .await
+            .unwrap_err();
+
+        assert_eq!(error, FetchError::Status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let (client, requests) = status_fixture(&[404, 200]).await;
+
+        let error = client.fetch_single_item(7).await.unwrap_err();
+
+        assert_eq!(error, FetchError::Status(StatusCode::NOT_FOUND));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    /// Serves each path's JSON body and 404s everything else.
+    async fn path_fixture(routes: &'static [(&'static str, &'static str)]) -> HackerNewsApi {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 1024];
+                let read = socket.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..read]);
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                let response = match routes.iter().find(|(route, _)| *route == path) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    ),
+                    None => {
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    }
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        HackerNewsApi::new()
+            .with_retries(0)
+            .with_base_url(format!("http://{address}"))
+    }
+
+    #[tokio::test]
+    async fn usernames_outside_the_hn_charset_never_reach_the_server() {
+        let (client, requests) = status_fixture(&[200]).await;
+
+        for username in ["../item/7", "a b", "alice?print=pretty", ""] {
+            assert!(
+                client.fetch_user(username).await.unwrap().is_none(),
+                "{username}"
+            );
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn following_feed_reports_activity_it_could_not_load() {
+        let client = path_fixture(&[
+            (
+                "/user/alice.json",
+                r#"{"id":"alice","submitted":[10,11,12]}"#,
+            ),
+            (
+                "/item/10.json",
+                r#"{"id":10,"type":"comment","parent":20,"time":3}"#,
+            ),
+            ("/item/11.json", r#"{"id":11,"type":"story","time":2}"#),
+        ])
+        .await;
+
+        let following = client
+            .fetch_following_story_ids(&["alice".to_string()], 10)
+            .await
+            .unwrap();
+
+        assert_eq!(following.story_ids, vec![11]);
+        assert_eq!(following.failed, vec![12, 20]);
+    }
+
+    #[test]
+    fn backoff_grows_with_jitter_up_to_the_cap() {
+        for attempt in 0..3 {
+            let ceiling = RETRY_BASE_DELAY_MS << attempt;
+            let delay = backoff_delay(attempt).as_millis() as u64;
+            assert!(
+                (ceiling / 2..=ceiling).contains(&delay),
+                "{attempt}: {delay}"
+            );
+        }
+        assert!(backoff_delay(30).as_millis() as u64 <= RETRY_MAX_DELAY_MS);
+    }
+
+    #[test]
+    fn failed_comments_can_be_retried() {
+        let mut thread = CommentThread::new(vec![1, 2], 2);
+        thread.pending.clear();
+        thread.failed_ids.extend([1, 2]);
+        assert_eq!(thread.failed_count(), 2);
+
+        assert_eq!(thread.retry_failed(), 2);
+        assert_eq!(thread.failed_count(), 0);
+        let mut pending = thread.pending.clone();
+        pending.sort_unstable();
+        assert_eq!(pending, vec![1, 2]);
+    }
+
     #[test]
     fn build_comments_from_cache_respects_limit() {
         let root_kids = vec![1, 2];
@@ -488,10 +1323,32 @@ mod tests {
         second.text = Some("second".to_string());
         items_by_id.insert(2, second);
 
-        let comments =
-            build_comments_from_cache(&root_kids, 1, &items_by_id, &HashSet::new()).unwrap();
+        let comments = build_comments_from_cache(
+            &root_kids,
+            1,
+            &items_by_id,
+            &HashSet::new(),
+            &MuteFilter::default(),
+            CommentOrder::Ranked,
+        )
+        .unwrap();
 
         assert_eq!(comments.len(), 1);
-        assert_eq!(comments[0].text, "first");
+        assert_eq!(blocks_to_plain_text(&comments[0].body), "first");
+    }
+
+    #[test]
+    fn user_deserializes_with_optional_fields() {
+        let user: Option<User> = serde_json::from_str(
+            r#"{"id":"pg","karma":157000,"created":1160418092,"submitted":[3,2,1]}"#,
+        )
+        .unwrap();
+        let user = user.unwrap();
+        assert_eq!(user.id, "pg");
+        assert_eq!(user.about, None);
+        assert_eq!(user.submitted, vec![3, 2, 1]);
+
+        let missing: Option<User> = serde_json::from_str("null").unwrap();
+        assert!(missing.is_none());
     }
 }
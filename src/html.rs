@@ -0,0 +1,558 @@
+use entities::ENTITIES;
+use std::{collections::HashMap, sync::OnceLock};
+
+const MAX_ENTITY_LEN: usize = 40;
+
+/// A block of comment text as produced by the HN HTML subset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    Paragraph(Vec<Inline>),
+    Preformatted(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inline {
+    Text(String),
+    Italic(String),
+    Link { text: String, href: String },
+    Break,
+}
+
+impl Inline {
+    /// Text shown for the inline. Links show their full target, since HN
+    /// truncates long URLs in the link text.
+    pub fn display_text(&self) -> String {
+        match self {
+            Self::Text(text) | Self::Italic(text) => text.clone(),
+            Self::Link { text, href } => {
+                let shortened = text.trim_end_matches("...");
+                if text == href || href.starts_with(shortened) {
+                    href.clone()
+                } else {
+                    format!("{text} ({href})")
+                }
+            }
+            Self::Break => "\n".to_string(),
+        }
+    }
+}
+
+/// Parses the HTML used in HN item text (`<p>`, `<i>`, `<a href>`, `<pre><code>`)
+/// into blocks. Unknown tags are dropped and their text kept.
+pub fn parse_hn_html(html: &str) -> Vec<Block> {
+    let mut parser = Parser::default();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            parser.text(rest);
+            break;
+        };
+        parser.text(&rest[..tag_start]);
+        rest = &rest[tag_start..];
+
+        let Some(tag_end) = rest.find('>') else {
+            parser.text(rest);
+            break;
+        };
+        parser.tag(&rest[1..tag_end]);
+        rest = &rest[tag_end + 1..];
+    }
+
+    parser.finish()
+}
+
+/// Flattens blocks into plain text, one line per paragraph or code line.
+pub fn blocks_to_plain_text(blocks: &[Block]) -> String {
+    blocks
+        .iter()
+        .map(|block| match block {
+            Block::Paragraph(inlines) => inlines
+                .iter()
+                .map(Inline::display_text)
+                .collect::<String>()
+                .lines()
+                .map(str::trim)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Block::Preformatted(text) => text.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Collects every URL in the blocks, in order and without duplicates: link
+/// targets plus bare `http(s)://` URLs in plain and preformatted text.
+pub fn block_urls(blocks: &[Block]) -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    let mut push = |url: String| {
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+    };
+
+    for block in blocks {
+        match block {
+            Block::Paragraph(inlines) => {
+                for inline in inlines {
+                    match inline {
+                        Inline::Link { href, .. } => push(href.clone()),
+                        Inline::Text(text) | Inline::Italic(text) => {
+                            bare_urls(text).for_each(&mut push)
+                        }
+                        Inline::Break => {}
+                    }
+                }
+            }
+            Block::Preformatted(text) => bare_urls(text).for_each(&mut push),
+        }
+    }
+
+    urls
+}
+
+fn bare_urls(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|ch: char| ch.is_whitespace() || matches!(ch, '<' | '>' | '"'))
+        .filter_map(|word| {
+            let start = word.find("https://").or_else(|| word.find("http://"))?;
+            let url = trim_url_end(&word[start..]);
+            let has_host = url
+                .split_once("://")
+                .is_some_and(|(_, rest)| !rest.is_empty());
+            has_host.then(|| url.to_string())
+        })
+}
+
+/// Strips sentence punctuation after a URL. A closing paren is only stripped
+/// when it has no opening partner in the URL, so `Rust_(language)` survives.
+fn trim_url_end(mut url: &str) -> &str {
+    loop {
+        let trimmed = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '\'']);
+        let unbalanced =
+            trimmed.ends_with(')') && trimmed.matches(')').count() > trimmed.matches('(').count();
+        if !unbalanced {
+            return trimmed;
+        }
+        url = &trimmed[..trimmed.len() - 1];
+    }
+}
+
+#[derive(Default)]
+struct Parser {
+    blocks: Vec<Block>,
+    inlines: Vec<Inline>,
+    italic: bool,
+    link_href: Option<String>,
+    link_text: String,
+    preformatted: Option<String>,
+}
+
+impl Parser {
+    fn text(&mut self, raw: &str) {
+        if raw.is_empty() {
+            return;
+        }
+
+        let decoded = decode_html_entities(raw);
+        if let Some(preformatted) = self.preformatted.as_mut() {
+            preformatted.push_str(&decoded);
+            return;
+        }
+
+        if self.link_href.is_some() {
+            self.link_text.push_str(&decoded);
+            return;
+        }
+
+        let mut lines = decoded.split('\n');
+        if let Some(first) = lines.next() {
+            self.push_text(first);
+        }
+        for line in lines {
+            self.push_break();
+            self.push_text(line);
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+
+        let collapsed = collapse_whitespace(text);
+        let merged = match (self.italic, self.inlines.last_mut()) {
+            (false, Some(Inline::Text(previous))) | (true, Some(Inline::Italic(previous))) => {
+                previous.push_str(&collapsed);
+                true
+            }
+            _ => false,
+        };
+        if !merged {
+            self.inlines.push(if self.italic {
+                Inline::Italic(collapsed)
+            } else {
+                Inline::Text(collapsed)
+            });
+        }
+    }
+
+    fn push_break(&mut self) {
+        if !self.inlines.is_empty() {
+            self.inlines.push(Inline::Break);
+        }
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let tag = tag.trim().trim_end_matches('/').trim();
+        let (closing, tag) = match tag.strip_prefix('/') {
+            Some(name) => (true, name.trim()),
+            None => (false, tag),
+        };
+        let name = tag
+            .split(|ch: char| ch.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        if self.preformatted.is_some() && !(closing && name == "pre") {
+            return;
+        }
+
+        match (name.as_str(), closing) {
+            // HN separates paragraphs with a bare `<p>`, so an opening tag only closes
+            // the current paragraph when it has content; `</p>` always does.
+            ("p", false) => self.close_open_paragraph(),
+            ("p", true) => self.end_paragraph(),
+            ("br", _) => self.push_break(),
+            ("i" | "em", closing) => self.italic = !closing,
+            ("a", false) => {
+                self.link_href = Some(attribute(tag, "href").unwrap_or_default());
+                self.link_text.clear();
+            }
+            ("a", true) => self.end_link(),
+            ("pre", false) => {
+                self.close_open_paragraph();
+                self.preformatted = Some(String::new());
+            }
+            ("pre", true) => self.end_preformatted(),
+            _ => {}
+        }
+    }
+
+    fn end_link(&mut self) {
+        let Some(href) = self.link_href.take() else {
+            return;
+        };
+        let text = collapse_whitespace(std::mem::take(&mut self.link_text).trim());
+        let href = if href.is_empty() { text.clone() } else { href };
+        if text.is_empty() && href.is_empty() {
+            return;
+        }
+        self.inlines.push(Inline::Link { text, href });
+    }
+
+    fn close_open_paragraph(&mut self) {
+        if !self.inlines.is_empty() || self.link_href.is_some() {
+            self.end_paragraph();
+        }
+    }
+
+    fn end_paragraph(&mut self) {
+        self.end_link();
+        while matches!(self.inlines.last(), Some(Inline::Break)) {
+            self.inlines.pop();
+        }
+        trim_inline_edges(&mut self.inlines);
+
+        let paragraph = Block::Paragraph(std::mem::take(&mut self.inlines));
+        let is_blank = matches!(&paragraph, Block::Paragraph(inlines) if inlines.is_empty());
+        let previous_blank =
+            matches!(self.blocks.last(), Some(Block::Paragraph(inlines)) if inlines.is_empty());
+        if is_blank && (self.blocks.is_empty() || previous_blank) {
+            return;
+        }
+        self.blocks.push(paragraph);
+    }
+
+    fn end_preformatted(&mut self) {
+        let Some(text) = self.preformatted.take() else {
+            return;
+        };
+        let text = text.trim_matches('\n').to_string();
+        if !text.is_empty() {
+            self.blocks.push(Block::Preformatted(text));
+        }
+    }
+
+    fn finish(mut self) -> Vec<Block> {
+        self.end_preformatted();
+        self.end_paragraph();
+        while matches!(self.blocks.last(), Some(Block::Paragraph(inlines)) if inlines.is_empty()) {
+            self.blocks.pop();
+        }
+        self.blocks
+    }
+}
+
+fn trim_inline_edges(inlines: &mut Vec<Inline>) {
+    if let Some(Inline::Text(text) | Inline::Italic(text)) = inlines.first_mut() {
+        *text = text.trim_start().to_string();
+    }
+    if let Some(Inline::Text(text) | Inline::Italic(text)) = inlines.last_mut() {
+        *text = text.trim_end().to_string();
+    }
+    inlines.retain(
+        |inline| !matches!(inline, Inline::Text(text) | Inline::Italic(text) if text.is_empty()),
+    );
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut last_was_space = false;
+    for ch in text.chars() {
+        if ch.is_whitespace() {
+            if !last_was_space {
+                collapsed.push(' ');
+            }
+            last_was_space = true;
+        } else {
+            collapsed.push(ch);
+            last_was_space = false;
+        }
+    }
+    collapsed
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let start = lower.find(&format!("{name}="))? + name.len() + 1;
+    let value = &tag[start..];
+    let value = match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+        _ => value.split(char::is_whitespace).next().unwrap_or_default(),
+    };
+    Some(decode_html_entities(value))
+}
+
+/// Decodes numeric (`&#8217;`, `&#x2014;`) and named HTML5 entities in a single
+/// pass, so decoded output is never decoded again (`&amp;lt;` becomes `&lt;`).
+/// Anything that is not a known, `;`-terminated entity is kept verbatim.
+pub fn decode_html_entities(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        match decode_entity(rest) {
+            Some((decoded, consumed)) => {
+                output.push_str(&decoded);
+                rest = &rest[consumed..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Decodes the entity at the start of `input`, returning the text and the number
+/// of bytes consumed.
+fn decode_entity(input: &str) -> Option<(String, usize)> {
+    let end = input
+        .char_indices()
+        .take(MAX_ENTITY_LEN)
+        .find(|(_, ch)| *ch == ';')?
+        .0;
+    let body = &input[1..end];
+
+    let decoded = match body.strip_prefix('#') {
+        Some(number) => {
+            let (digits, radix) = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => (hex, 16),
+                None => (number, 10),
+            };
+            if digits.is_empty() || !digits.chars().all(|ch| ch.is_digit(radix)) {
+                return None;
+            }
+            u32::from_str_radix(digits, radix)
+                .ok()
+                .and_then(char::from_u32)
+                .filter(|ch| *ch != '\0')
+                .unwrap_or(char::REPLACEMENT_CHARACTER)
+                .to_string()
+        }
+        None => {
+            if body.is_empty() || !body.chars().all(|ch| ch.is_ascii_alphanumeric()) {
+                return None;
+            }
+            named_entities().get(&input[..=end])?.to_string()
+        }
+    };
+
+    Some((decoded, end + 1))
+}
+
+fn named_entities() -> &'static HashMap<&'static str, &'static str> {
+    static NAMED_ENTITIES: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    NAMED_ENTITIES.get_or_init(|| {
+        ENTITIES
+            .iter()
+            .filter(|entity| entity.entity.ends_with(';'))
+            .map(|entity| (entity.entity, entity.characters))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(value: &str) -> Inline {
+        Inline::Text(value.to_string())
+    }
+
+    fn plain_text(html: &str) -> String {
+        blocks_to_plain_text(&parse_hn_html(html))
+    }
+
+    #[test]
+    fn plain_text_normalizes_html_and_entities() {
+        let cleaned =
+            plain_text("<p>Hello &amp; <em>world</em></p><p>Line 2</p><br />&quot;quote&quot;");
+
+        assert_eq!(cleaned, "Hello & world\nLine 2\n\"quote\"");
+    }
+
+    #[test]
+    fn plain_text_collapses_extra_blank_lines() {
+        let cleaned = plain_text("<p>One</p><p></p><p></p><p>Two</p>");
+
+        assert_eq!(cleaned, "One\n\nTwo");
+    }
+
+    #[test]
+    fn parses_paragraphs_italics_and_links() {
+        let blocks = parse_hn_html(
+            "First <i>really</i> good<p>See <a href=\"https:&#x2F;&#x2F;example.com&#x2F;a\" rel=\"nofollow\">https:&#x2F;&#x2F;example.com&#x2F;a</a>.",
+        );
+
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Paragraph(vec![
+                    text("First "),
+                    Inline::Italic("really".to_string()),
+                    text(" good"),
+                ]),
+                Block::Paragraph(vec![
+                    text("See "),
+                    Inline::Link {
+                        text: "https://example.com/a".to_string(),
+                        href: "https://example.com/a".to_string(),
+                    },
+                    text("."),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn preformatted_blocks_keep_whitespace() {
+        let blocks = parse_hn_html(
+            "Code:<p><pre><code>  fn main() {\n      x &lt; 1\n  }\n</code></pre>After",
+        );
+
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Paragraph(vec![text("Code:")]),
+                Block::Preformatted("  fn main() {\n      x < 1\n  }".to_string()),
+                Block::Paragraph(vec![text("After")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn link_display_text_shows_full_target() {
+        let truncated = Inline::Link {
+            text: "https://example.com/very/lo...".to_string(),
+            href: "https://example.com/very/long/path".to_string(),
+        };
+        assert_eq!(
+            truncated.display_text(),
+            "https://example.com/very/long/path"
+        );
+
+        let labelled = Inline::Link {
+            text: "docs".to_string(),
+            href: "https://example.com".to_string(),
+        };
+        assert_eq!(labelled.display_text(), "docs (https://example.com)");
+    }
+
+    #[test]
+    fn plain_text_keeps_code_indentation() {
+        let blocks = parse_hn_html("Intro<pre><code>  indented</code></pre>");
+        assert_eq!(blocks_to_plain_text(&blocks), "Intro\n  indented");
+    }
+
+    #[test]
+    fn decodes_numeric_and_named_entities() {
+        assert_eq!(
+            decode_html_entities("a&#x2014;b &hellip; c&nbsp;d it&#8217;s &eacute;&#X41;"),
+            "a\u{2014}b \u{2026} c\u{a0}d it\u{2019}s \u{e9}A"
+        );
+    }
+
+    #[test]
+    fn decodes_in_a_single_pass() {
+        assert_eq!(decode_html_entities("&amp;lt;p&amp;gt;"), "&lt;p&gt;");
+    }
+
+    #[test]
+    fn leaves_unknown_or_malformed_entities_untouched() {
+        assert_eq!(
+            decode_html_entities("AT&T &bogus; &#xZZ; &#; & done &"),
+            "AT&T &bogus; &#xZZ; &#; & done &"
+        );
+        assert_eq!(decode_html_entities("&#0;&#x110000;"), "\u{fffd}\u{fffd}");
+    }
+
+    #[test]
+    fn collects_link_targets_and_bare_urls_once() {
+        let blocks = parse_hn_html(
+            "See <a href=\"https://a.example/x\">docs</a> or (https://b.example/y).<p>Again https://a.example/x<pre><code>curl http://c.example/z\n</code></pre>",
+        );
+
+        assert_eq!(
+            block_urls(&blocks),
+            vec![
+                "https://a.example/x".to_string(),
+                "https://b.example/y".to_string(),
+                "http://c.example/z".to_string(),
+            ]
+        );
+        assert!(block_urls(&parse_hn_html("no links, just https:// here")).is_empty());
+    }
+
+    #[test]
+    fn bare_urls_keep_balanced_parens() {
+        let blocks = parse_hn_html(
+            "Read https://en.wikipedia.org/wiki/Rust_(programming_language). Or (https://en.wikipedia.org/wiki/C_(language)), fine",
+        );
+
+        assert_eq!(
+            block_urls(&blocks),
+            vec![
+                "https://en.wikipedia.org/wiki/Rust_(programming_language)".to_string(),
+                "https://en.wikipedia.org/wiki/C_(language)".to_string(),
+            ]
+        );
+    }
+}
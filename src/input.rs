@@ -7,7 +7,48 @@ pub enum GlobalKeyAction {
     FocusPreviousPane,
     PaneShortcut(char),
     Refresh,
+    RetryFailed,
     Quit,
+    OpenCommandPalette,
+    OpenItemPrompt,
+}
+
+impl GlobalKeyAction {
+    pub const ALL: [Self; 13] = [
+        Self::Exit,
+        Self::FocusNextPane,
+        Self::FocusPreviousPane,
+        Self::PaneShortcut('1'),
+        Self::PaneShortcut('2'),
+        Self::PaneShortcut('3'),
+        Self::PaneShortcut('4'),
+        Self::PaneShortcut('5'),
+        Self::PaneShortcut('6'),
+        Self::Refresh,
+        Self::RetryFailed,
+        Self::Quit,
+        Self::OpenItemPrompt,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Exit => "Exit immediately",
+            Self::FocusNextPane => "Focus next pane",
+            Self::FocusPreviousPane => "Focus previous pane",
+            Self::PaneShortcut('1') => "Focus bookmarks",
+            Self::PaneShortcut('2') => "Focus posts",
+            Self::PaneShortcut('3') => "Focus comments",
+            Self::PaneShortcut('4') => "Focus feeds",
+            Self::PaneShortcut('5') => "Focus reader",
+            Self::PaneShortcut('6') => "Focus alerts",
+            Self::PaneShortcut(_) => "Focus pane",
+            Self::Refresh => "Refresh feed",
+            Self::RetryFailed => "Retry failed items",
+            Self::Quit => "Quit",
+            Self::OpenCommandPalette => "Open command palette",
+            Self::OpenItemPrompt => "Open item by ID or URL",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,18 +65,175 @@ pub enum PostsKeyAction {
     BookmarkSelected,
     OpenComments,
     OpenPost,
+    OpenDiscussion,
+    OpenReader,
+    ViewAuthor,
+    CopyUrl,
+    CopyDiscussionUrl,
+    MuteAuthor,
+    MuteDomain,
+    FilterDomain,
+    CycleSort,
+    CycleMinPoints,
+    CycleMinComments,
+    CycleMaxAge,
+    ToggleHideJobs,
+    ResetView,
     CloseComments,
 }
 
+impl PostsKeyAction {
+    pub const ALL: [Self; 20] = [
+        Self::SelectPrevious,
+        Self::SelectNextAndLoadMore,
+        Self::BookmarkSelected,
+        Self::OpenComments,
+        Self::OpenPost,
+        Self::OpenDiscussion,
+        Self::OpenReader,
+        Self::ViewAuthor,
+        Self::CopyUrl,
+        Self::CopyDiscussionUrl,
+        Self::MuteAuthor,
+        Self::MuteDomain,
+        Self::FilterDomain,
+        Self::CycleSort,
+        Self::CycleMinPoints,
+        Self::CycleMinComments,
+        Self::CycleMaxAge,
+        Self::ToggleHideJobs,
+        Self::ResetView,
+        Self::CloseComments,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::SelectPrevious => "Select previous post",
+            Self::SelectNextAndLoadMore => "Select next post",
+            Self::BookmarkSelected => "Bookmark selected post",
+            Self::OpenComments => "Open comments",
+            Self::OpenPost => "Open article in browser",
+            Self::OpenDiscussion => "Open discussion in browser",
+            Self::OpenReader => "Read article",
+            Self::ViewAuthor => "View author profile",
+            Self::CopyUrl => "Copy post URL",
+            Self::CopyDiscussionUrl => "Copy discussion URL",
+            Self::MuteAuthor => "Mute post author",
+            Self::MuteDomain => "Mute post domain",
+            Self::FilterDomain => "Show posts from this domain",
+            Self::CycleSort => "Change post sort order",
+            Self::CycleMinPoints => "Change minimum points",
+            Self::CycleMinComments => "Change minimum comments",
+            Self::CycleMaxAge => "Change maximum age",
+            Self::ToggleHideJobs => "Hide or show jobs",
+            Self::ResetView => "Reset sort and filters",
+            Self::CloseComments => "Close comments",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CommentsKeyAction {
     Close,
     BookmarkPost,
     OpenPost,
+    OpenDiscussion,
+    OpenReader,
+    ViewAuthor,
+    OpenCommentLinks,
+    OpenThreadLinks,
+    CopyPermalink,
+    CopyText,
+    MuteAuthor,
+    MuteDomain,
+    ToggleMuted,
+    CycleOrder,
     JumpPrevious,
     JumpNext,
     JumpPreviousSibling,
     JumpNextSibling,
+    JumpNextOp,
+    JumpParent,
+    JumpTopLevel,
+    JumpNextThread,
+    ScrollUp,
+    ScrollDown,
+    ScrollPageUp,
+    ScrollPageDown,
+    ScrollHome,
+    ScrollEnd,
+}
+
+impl CommentsKeyAction {
+    pub const ALL: [Self; 28] = [
+        Self::Close,
+        Self::BookmarkPost,
+        Self::OpenPost,
+        Self::OpenDiscussion,
+        Self::OpenReader,
+        Self::ViewAuthor,
+        Self::OpenCommentLinks,
+        Self::OpenThreadLinks,
+        Self::CopyPermalink,
+        Self::CopyText,
+        Self::MuteAuthor,
+        Self::MuteDomain,
+        Self::ToggleMuted,
+        Self::CycleOrder,
+        Self::JumpPrevious,
+        Self::JumpNext,
+        Self::JumpPreviousSibling,
+        Self::JumpNextSibling,
+        Self::JumpNextOp,
+        Self::JumpParent,
+        Self::JumpTopLevel,
+        Self::JumpNextThread,
+        Self::ScrollUp,
+        Self::ScrollDown,
+        Self::ScrollPageUp,
+        Self::ScrollPageDown,
+        Self::ScrollHome,
+        Self::ScrollEnd,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Close => "Close comments",
+            Self::BookmarkPost => "Bookmark post",
+            Self::OpenPost => "Open article in browser",
+            Self::OpenDiscussion => "Open discussion in browser",
+            Self::OpenReader => "Read article",
+            Self::ViewAuthor => "View comment author profile",
+            Self::OpenCommentLinks => "Open link in comment",
+            Self::OpenThreadLinks => "Open link in thread",
+            Self::CopyPermalink => "Copy comment link",
+            Self::CopyText => "Copy comment text",
+            Self::MuteAuthor => "Mute comment author",
+            Self::MuteDomain => "Mute post domain",
+            Self::ToggleMuted => "Show or hide muted comment",
+            Self::CycleOrder => "Change comment order",
+            Self::JumpPrevious => "Jump to previous comment",
+            Self::JumpNext => "Jump to next comment",
+            Self::JumpPreviousSibling => "Jump to previous sibling",
+            Self::JumpNextSibling => "Jump to next sibling",
+            Self::JumpNextOp => "Jump to next OP comment",
+            Self::JumpParent => "Jump to parent comment",
+            Self::JumpTopLevel => "Jump to top-level comment",
+            Self::JumpNextThread => "Jump to next thread",
+            Self::ScrollUp => "Scroll up",
+            Self::ScrollDown => "Scroll down",
+            Self::ScrollPageUp => "Scroll page up",
+            Self::ScrollPageDown => "Scroll page down",
+            Self::ScrollHome => "Scroll to top",
+            Self::ScrollEnd => "Scroll to bottom",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReaderKeyAction {
+    Close,
+    OpenInBrowser,
     ScrollUp,
     ScrollDown,
     ScrollPageUp,
@@ -44,6 +242,32 @@ pub enum CommentsKeyAction {
     ScrollEnd,
 }
 
+impl ReaderKeyAction {
+    pub const ALL: [Self; 8] = [
+        Self::Close,
+        Self::OpenInBrowser,
+        Self::ScrollUp,
+        Self::ScrollDown,
+        Self::ScrollPageUp,
+        Self::ScrollPageDown,
+        Self::ScrollHome,
+        Self::ScrollEnd,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Close => "Close reader",
+            Self::OpenInBrowser => "Open article in browser",
+            Self::ScrollUp => "Scroll up",
+            Self::ScrollDown => "Scroll down",
+            Self::ScrollPageUp => "Scroll page up",
+            Self::ScrollPageDown => "Scroll page down",
+            Self::ScrollHome => "Scroll to top",
+            Self::ScrollEnd => "Scroll to bottom",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BookmarksKeyAction {
     Expand,
@@ -57,6 +281,97 @@ pub enum BookmarksKeyAction {
     Delete,
 }
 
+impl BookmarksKeyAction {
+    pub const ALL: [Self; 9] = [
+        Self::Expand,
+        Self::Close,
+        Self::BookmarkSelected,
+        Self::SelectPrevious,
+        Self::SelectNext,
+        Self::OpenComments,
+        Self::OpenPost,
+        Self::OpenAll,
+        Self::Delete,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Expand => "Expand bookmarks",
+            Self::Close => "Collapse bookmarks",
+            Self::BookmarkSelected => "Bookmark selected post",
+            Self::SelectPrevious => "Select previous bookmark",
+            Self::SelectNext => "Select next bookmark",
+            Self::OpenComments => "Open bookmark comments",
+            Self::OpenPost => "Open bookmark in browser",
+            Self::OpenAll => "Open all bookmarks in browser",
+            Self::Delete => "Delete bookmark",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteKeyAction {
+    Close,
+    Submit,
+    SelectPrevious,
+    SelectNext,
+    DeleteChar,
+    Insert(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkPickerKeyAction {
+    Close,
+    Submit,
+    SelectPrevious,
+    SelectNext,
+    ToggleScope,
+    DeleteHint,
+    Hint(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileKeyAction {
+    Close,
+    SelectPrevious,
+    SelectNext,
+    OpenSubmission,
+    OpenInBrowser,
+    ToggleFollow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertsKeyAction {
+    SelectPrevious,
+    SelectNext,
+    OpenComments,
+    OpenPost,
+    Dismiss,
+    ClearAll,
+}
+
+impl AlertsKeyAction {
+    pub const ALL: [Self; 6] = [
+        Self::SelectPrevious,
+        Self::SelectNext,
+        Self::OpenComments,
+        Self::OpenPost,
+        Self::Dismiss,
+        Self::ClearAll,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::SelectPrevious => "Select previous alert",
+            Self::SelectNext => "Select next alert",
+            Self::OpenComments => "Open alert comments",
+            Self::OpenPost => "Open alert in browser",
+            Self::Dismiss => "Dismiss alert",
+            Self::ClearAll => "Clear all alerts",
+        }
+    }
+}
+
 pub fn map_global_action(key_event: KeyEvent) -> Option<GlobalKeyAction> {
     if matches!(key_event.code, KeyCode::Char('c'))
         && key_event.modifiers.contains(KeyModifiers::CONTROL)
@@ -64,12 +379,21 @@ pub fn map_global_action(key_event: KeyEvent) -> Option<GlobalKeyAction> {
         return Some(GlobalKeyAction::Exit);
     }
 
+    if matches!(key_event.code, KeyCode::Char('p'))
+        && key_event.modifiers.contains(KeyModifiers::CONTROL)
+    {
+        return Some(GlobalKeyAction::OpenCommandPalette);
+    }
+
     match key_event.code {
         KeyCode::Tab => Some(GlobalKeyAction::FocusNextPane),
         KeyCode::BackTab => Some(GlobalKeyAction::FocusPreviousPane),
-        KeyCode::Char(shortcut @ '1'..='4') => Some(GlobalKeyAction::PaneShortcut(shortcut)),
-        KeyCode::Char('r') | KeyCode::Char('R') => Some(GlobalKeyAction::Refresh),
+        KeyCode::Char(shortcut @ '1'..='6') => Some(GlobalKeyAction::PaneShortcut(shortcut)),
+        KeyCode::Char('r') => Some(GlobalKeyAction::Refresh),
+        KeyCode::Char('R') => Some(GlobalKeyAction::RetryFailed),
         KeyCode::Char('q') => Some(GlobalKeyAction::Quit),
+        KeyCode::Char(':') => Some(GlobalKeyAction::OpenCommandPalette),
+        KeyCode::Char('g') => Some(GlobalKeyAction::OpenItemPrompt),
         _ => None,
     }
 }
@@ -98,6 +422,20 @@ pub fn map_posts_action(key_code: KeyCode, comments_open: bool) -> Option<PostsK
         KeyCode::Char('b') | KeyCode::Char('B') => Some(PostsKeyAction::BookmarkSelected),
         KeyCode::Enter => Some(PostsKeyAction::OpenComments),
         KeyCode::Char('o') | KeyCode::Char('O') => Some(PostsKeyAction::OpenPost),
+        KeyCode::Char('d') | KeyCode::Char('D') => Some(PostsKeyAction::OpenDiscussion),
+        KeyCode::Char('v') | KeyCode::Char('V') => Some(PostsKeyAction::OpenReader),
+        KeyCode::Char('u') | KeyCode::Char('U') => Some(PostsKeyAction::ViewAuthor),
+        KeyCode::Char('y') => Some(PostsKeyAction::CopyUrl),
+        KeyCode::Char('Y') => Some(PostsKeyAction::CopyDiscussionUrl),
+        KeyCode::Char('m') => Some(PostsKeyAction::MuteAuthor),
+        KeyCode::Char('M') => Some(PostsKeyAction::MuteDomain),
+        KeyCode::Char('f') | KeyCode::Char('F') => Some(PostsKeyAction::FilterDomain),
+        KeyCode::Char('s') | KeyCode::Char('S') => Some(PostsKeyAction::CycleSort),
+        KeyCode::Char('p') | KeyCode::Char('P') => Some(PostsKeyAction::CycleMinPoints),
+        KeyCode::Char('c') | KeyCode::Char('C') => Some(PostsKeyAction::CycleMinComments),
+        KeyCode::Char('a') | KeyCode::Char('A') => Some(PostsKeyAction::CycleMaxAge),
+        KeyCode::Char('x') | KeyCode::Char('X') => Some(PostsKeyAction::ToggleHideJobs),
+        KeyCode::Char('z') | KeyCode::Char('Z') => Some(PostsKeyAction::ResetView),
         KeyCode::Esc if comments_open => Some(PostsKeyAction::CloseComments),
         _ => None,
     }
@@ -108,6 +446,17 @@ pub fn map_comments_action(key_code: KeyCode) -> Option<CommentsKeyAction> {
         KeyCode::Esc => Some(CommentsKeyAction::Close),
         KeyCode::Char('b') | KeyCode::Char('B') => Some(CommentsKeyAction::BookmarkPost),
         KeyCode::Char('o') | KeyCode::Char('O') => Some(CommentsKeyAction::OpenPost),
+        KeyCode::Char('d') | KeyCode::Char('D') => Some(CommentsKeyAction::OpenDiscussion),
+        KeyCode::Char('v') | KeyCode::Char('V') => Some(CommentsKeyAction::OpenReader),
+        KeyCode::Char('u') | KeyCode::Char('U') => Some(CommentsKeyAction::ViewAuthor),
+        KeyCode::Char('f') => Some(CommentsKeyAction::OpenCommentLinks),
+        KeyCode::Char('F') => Some(CommentsKeyAction::OpenThreadLinks),
+        KeyCode::Char('y') => Some(CommentsKeyAction::CopyPermalink),
+        KeyCode::Char('Y') => Some(CommentsKeyAction::CopyText),
+        KeyCode::Char('m') => Some(CommentsKeyAction::MuteAuthor),
+        KeyCode::Char('M') => Some(CommentsKeyAction::MuteDomain),
+        KeyCode::Char('x') | KeyCode::Char('X') => Some(CommentsKeyAction::ToggleMuted),
+        KeyCode::Char('s') | KeyCode::Char('S') => Some(CommentsKeyAction::CycleOrder),
         KeyCode::Up => Some(CommentsKeyAction::JumpPrevious),
         KeyCode::Down => Some(CommentsKeyAction::JumpNext),
         KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('H') => {
@@ -116,6 +465,10 @@ pub fn map_comments_action(key_code: KeyCode) -> Option<CommentsKeyAction> {
         KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('L') => {
             Some(CommentsKeyAction::JumpNextSibling)
         }
+        KeyCode::Char('n') | KeyCode::Char('N') => Some(CommentsKeyAction::JumpNextOp),
+        KeyCode::Char('p') | KeyCode::Char('P') => Some(CommentsKeyAction::JumpParent),
+        KeyCode::Char('t') => Some(CommentsKeyAction::JumpTopLevel),
+        KeyCode::Char('T') => Some(CommentsKeyAction::JumpNextThread),
         KeyCode::Char('k') | KeyCode::Char('K') => Some(CommentsKeyAction::ScrollUp),
         KeyCode::Char('j') | KeyCode::Char('J') => Some(CommentsKeyAction::ScrollDown),
         KeyCode::PageUp => Some(CommentsKeyAction::ScrollPageUp),
@@ -126,6 +479,22 @@ pub fn map_comments_action(key_code: KeyCode) -> Option<CommentsKeyAction> {
     }
 }
 
+pub fn map_reader_action(key_code: KeyCode) -> Option<ReaderKeyAction> {
+    match key_code {
+        KeyCode::Esc => Some(ReaderKeyAction::Close),
+        KeyCode::Char('o') | KeyCode::Char('O') => Some(ReaderKeyAction::OpenInBrowser),
+        KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => Some(ReaderKeyAction::ScrollUp),
+        KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => {
+            Some(ReaderKeyAction::ScrollDown)
+        }
+        KeyCode::PageUp => Some(ReaderKeyAction::ScrollPageUp),
+        KeyCode::PageDown | KeyCode::Char(' ') => Some(ReaderKeyAction::ScrollPageDown),
+        KeyCode::Home => Some(ReaderKeyAction::ScrollHome),
+        KeyCode::End => Some(ReaderKeyAction::ScrollEnd),
+        _ => None,
+    }
+}
+
 pub fn map_bookmarks_action(
     key_code: KeyCode,
     bookmarks_collapsed: bool,
@@ -159,6 +528,69 @@ pub fn map_bookmarks_action(
     }
 }
 
+pub fn map_palette_action(key_event: KeyEvent) -> Option<PaletteKeyAction> {
+    if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+        return match key_event.code {
+            KeyCode::Char('p') => Some(PaletteKeyAction::SelectPrevious),
+            KeyCode::Char('n') => Some(PaletteKeyAction::SelectNext),
+            _ => None,
+        };
+    }
+
+    match key_event.code {
+        KeyCode::Esc => Some(PaletteKeyAction::Close),
+        KeyCode::Enter => Some(PaletteKeyAction::Submit),
+        KeyCode::Up => Some(PaletteKeyAction::SelectPrevious),
+        KeyCode::Down => Some(PaletteKeyAction::SelectNext),
+        KeyCode::Backspace => Some(PaletteKeyAction::DeleteChar),
+        KeyCode::Char(ch) => Some(PaletteKeyAction::Insert(ch)),
+        _ => None,
+    }
+}
+
+pub fn map_profile_action(key_code: KeyCode) -> Option<ProfileKeyAction> {
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('u') => Some(ProfileKeyAction::Close),
+        KeyCode::Up | KeyCode::Char('k') => Some(ProfileKeyAction::SelectPrevious),
+        KeyCode::Down | KeyCode::Char('j') => Some(ProfileKeyAction::SelectNext),
+        KeyCode::Enter => Some(ProfileKeyAction::OpenSubmission),
+        KeyCode::Char('o') | KeyCode::Char('O') => Some(ProfileKeyAction::OpenInBrowser),
+        KeyCode::Char('f') | KeyCode::Char('F') => Some(ProfileKeyAction::ToggleFollow),
+        _ => None,
+    }
+}
+
+pub fn map_alerts_action(key_code: KeyCode) -> Option<AlertsKeyAction> {
+    match key_code {
+        KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('K') => {
+            Some(AlertsKeyAction::SelectPrevious)
+        }
+        KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('J') => {
+            Some(AlertsKeyAction::SelectNext)
+        }
+        KeyCode::Enter => Some(AlertsKeyAction::OpenComments),
+        KeyCode::Char('o') | KeyCode::Char('O') => Some(AlertsKeyAction::OpenPost),
+        KeyCode::Char('d') | KeyCode::Delete | KeyCode::Backspace => Some(AlertsKeyAction::Dismiss),
+        KeyCode::Char('D') => Some(AlertsKeyAction::ClearAll),
+        _ => None,
+    }
+}
+
+pub fn map_link_picker_action(key_code: KeyCode) -> Option<LinkPickerKeyAction> {
+    match key_code {
+        KeyCode::Esc | KeyCode::Char('q') => Some(LinkPickerKeyAction::Close),
+        KeyCode::Enter => Some(LinkPickerKeyAction::Submit),
+        KeyCode::Up | KeyCode::Char('k') => Some(LinkPickerKeyAction::SelectPrevious),
+        KeyCode::Down | KeyCode::Char('j') => Some(LinkPickerKeyAction::SelectNext),
+        KeyCode::Tab | KeyCode::Char('f') | KeyCode::Char('F') => {
+            Some(LinkPickerKeyAction::ToggleScope)
+        }
+        KeyCode::Backspace => Some(LinkPickerKeyAction::DeleteHint),
+        KeyCode::Char(ch) if ch.is_ascii_digit() => Some(LinkPickerKeyAction::Hint(ch)),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +605,19 @@ mod tests {
             map_global_action(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE)),
             Some(GlobalKeyAction::PaneShortcut('2'))
         );
+        assert_eq!(
+            map_global_action(KeyEvent::new(KeyCode::Char('5'), KeyModifiers::NONE)),
+            Some(GlobalKeyAction::PaneShortcut('5'))
+        );
+        assert!(GlobalKeyAction::ALL.contains(&GlobalKeyAction::PaneShortcut('5')));
+        assert_eq!(
+            map_global_action(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
+            Some(GlobalKeyAction::OpenCommandPalette)
+        );
+        assert_eq!(
+            map_global_action(KeyEvent::new(KeyCode::Char(':'), KeyModifiers::NONE)),
+            Some(GlobalKeyAction::OpenCommandPalette)
+        );
         assert_eq!(
             map_global_action(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE)),
             None
@@ -195,4 +640,33 @@ mod tests {
             Some(BookmarksKeyAction::OpenAll)
         );
     }
+
+    #[test]
+    fn palette_mapping_treats_plain_chars_as_input() {
+        assert_eq!(
+            map_palette_action(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)),
+            Some(PaletteKeyAction::Insert('q'))
+        );
+        assert_eq!(
+            map_palette_action(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL)),
+            Some(PaletteKeyAction::SelectNext)
+        );
+        assert_eq!(
+            map_palette_action(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+            Some(PaletteKeyAction::Close)
+        );
+    }
+
+    #[test]
+    fn link_picker_mapping_only_accepts_digit_hints() {
+        assert_eq!(
+            map_link_picker_action(KeyCode::Char('3')),
+            Some(LinkPickerKeyAction::Hint('3'))
+        );
+        assert_eq!(
+            map_link_picker_action(KeyCode::Tab),
+            Some(LinkPickerKeyAction::ToggleScope)
+        );
+        assert_eq!(map_link_picker_action(KeyCode::Char('x')), None);
+    }
 }
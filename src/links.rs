@@ -0,0 +1,221 @@
+use crate::{hn::Comment, html::block_urls};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkScope {
+    Comment,
+    Thread,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkEntry {
+    pub hint: String,
+    pub url: String,
+    pub author: String,
+}
+
+/// Popup listing the URLs of the focused comment (or the whole thread), each
+/// labelled with a numeric hint that opens it once typed in full.
+#[derive(Debug, Clone)]
+pub struct LinkPicker {
+    scope: LinkScope,
+    comment_author: Option<String>,
+    comment_links: Vec<LinkEntry>,
+    thread_links: Vec<LinkEntry>,
+    typed: String,
+    selected: usize,
+}
+
+impl LinkPicker {
+    pub fn new(comments: &[Comment], focused: Option<usize>, scope: LinkScope) -> Self {
+        let focused = focused.and_then(|index| comments.get(index));
+        let comment_links = focused
+            .map(|comment| with_hints(author_urls(comment).collect()))
+            .unwrap_or_default();
+
+        let mut thread_urls: Vec<(String, String)> = Vec::new();
+        for (author, url) in comments.iter().flat_map(author_urls) {
+            if !thread_urls.iter().any(|(_, seen)| *seen == url) {
+                thread_urls.push((author, url));
+            }
+        }
+
+        Self {
+            scope,
+            comment_author: focused.map(|comment| comment.author.clone()),
+            comment_links,
+            thread_links: with_hints(thread_urls),
+            typed: String::new(),
+            selected: 0,
+        }
+    }
+
+    pub fn scope(&self) -> LinkScope {
+        self.scope
+    }
+
+    pub fn title(&self) -> String {
+        match (self.scope, self.comment_author.as_deref()) {
+            (LinkScope::Comment, Some(author)) => format!("Links in comment by {author}"),
+            (LinkScope::Comment, None) => "Links in comment".to_string(),
+            (LinkScope::Thread, _) => "Links in thread".to_string(),
+        }
+    }
+
+    pub fn typed(&self) -> &str {
+        &self.typed
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    fn entries(&self) -> &[LinkEntry] {
+        match self.scope {
+            LinkScope::Comment => &self.comment_links,
+            LinkScope::Thread => &self.thread_links,
+        }
+    }
+
+    /// Entries whose hint starts with the digits typed so far.
+    pub fn visible(&self) -> Vec<&LinkEntry> {
+        self.entries()
+            .iter()
+            .filter(|entry| entry.hint.starts_with(&self.typed))
+            .collect()
+    }
+
+    pub fn toggle_scope(&mut self) {
+        self.scope = match self.scope {
+            LinkScope::Comment => LinkScope::Thread,
+            LinkScope::Thread => LinkScope::Comment,
+        };
+        self.typed.clear();
+        self.selected = 0;
+    }
+
+    /// Adds a hint character, returning the URL once a hint is fully typed.
+    /// Characters that match no hint are ignored.
+    pub fn push_hint(&mut self, ch: char) -> Option<String> {
+        self.typed.push(ch);
+        if self.visible().is_empty() {
+            self.typed.pop();
+            return None;
+        }
+        self.selected = 0;
+
+        self.entries()
+            .iter()
+            .find(|entry| entry.hint == self.typed)
+            .map(|entry| entry.url.clone())
+    }
+
+    pub fn pop_hint(&mut self) {
+        self.typed.pop();
+        self.selected = 0;
+    }
+
+    pub fn select_next(&mut self) {
+        let len = self.visible().len();
+        if len > 0 {
+            self.selected = (self.selected + 1) % len;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        let len = self.visible().len();
+        if len > 0 {
+            self.selected = (self.selected + len - 1) % len;
+        }
+    }
+
+    pub fn selected_url(&self) -> Option<String> {
+        self.visible()
+            .get(self.selected)
+            .map(|entry| entry.url.clone())
+    }
+}
+
+fn author_urls(comment: &Comment) -> impl Iterator<Item = (String, String)> + '_ {
+    block_urls(&comment.body)
+        .into_iter()
+        .map(|url| (comment.author.clone(), url))
+}
+
+fn with_hints(links: Vec<(String, String)>) -> Vec<LinkEntry> {
+    let labels = hint_labels(links.len());
+    links
+        .into_iter()
+        .zip(labels)
+        .map(|((author, url), hint)| LinkEntry { hint, url, author })
+        .collect()
+}
+
+/// Numeric hints of equal width, so no hint is a prefix of another: `1`-`9`
+/// for short lists, `01`-`99` and so on beyond that.
+pub fn hint_labels(count: usize) -> Vec<String> {
+    let width = count.to_string().len();
+    (1..=count)
+        .map(|number| format!("{number:0width$}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::parse_hn_html;
+
+    fn comment(author: &str, html: &str) -> Comment {
+        Comment {
+            id: 0,
+            author: author.to_string(),
+            body: parse_hn_html(html),
+            published_at: 0,
+            depth: 0,
+            ancestor_has_next_sibling: Vec::new(),
+            is_last_sibling: true,
+            muted: false,
+        }
+    }
+
+    #[test]
+    fn hint_labels_share_a_width() {
+        assert_eq!(hint_labels(3), vec!["1", "2", "3"]);
+        let labels = hint_labels(12);
+        assert_eq!(labels.first().map(String::as_str), Some("01"));
+        assert_eq!(labels.last().map(String::as_str), Some("12"));
+    }
+
+    #[test]
+    fn typing_a_full_hint_returns_its_url() {
+        let comments = vec![
+            comment("alice", "https://a.example and https://b.example"),
+            comment("bob", "https://a.example again, plus https://c.example"),
+        ];
+        let mut picker = LinkPicker::new(&comments, Some(0), LinkScope::Comment);
+
+        assert_eq!(picker.visible().len(), 2);
+        assert_eq!(picker.push_hint('9'), None);
+        assert_eq!(picker.typed(), "");
+        assert_eq!(picker.push_hint('2').as_deref(), Some("https://b.example"));
+
+        picker.toggle_scope();
+        let urls: Vec<&str> = picker
+            .visible()
+            .iter()
+            .map(|entry| entry.url.as_str())
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://a.example",
+                "https://b.example",
+                "https://c.example"
+            ]
+        );
+        assert_eq!(picker.visible()[2].author, "bob");
+    }
+}
@@ -1,11 +1,23 @@
 use crate::app::App;
 
 mod app;
+mod clipboard;
 mod comments_nav;
+mod config;
 mod event;
 mod hn;
+mod html;
 mod input;
+mod links;
+mod mute;
+mod opener;
+mod palette;
+mod posts_view;
+mod profile;
+mod reader;
+mod state;
 mod ui;
+mod watch;
 
 #[tokio::main]
 async fn main() -> color_eyre::Result<()> {
@@ -0,0 +1,154 @@
+use crate::hn::url_domain;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+/// Persisted mute lists. Authors match exactly, domains also match their
+/// subdomains, and keywords are case-insensitive title substrings unless
+/// wrapped in slashes, e.g. `/^Show HN/`, which makes them regexes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MuteRules {
+    pub authors: Vec<String>,
+    pub domains: Vec<String>,
+    pub keywords: Vec<String>,
+}
+
+impl MuteRules {
+    /// Adds `author`, returning `false` when it was already muted.
+    pub fn mute_author(&mut self, author: &str) -> bool {
+        add_unique(&mut self.authors, author)
+    }
+
+    /// Adds `domain` without a leading `www.`, returning `false` when it was
+    /// already muted.
+    pub fn mute_domain(&mut self, domain: &str) -> bool {
+        let domain = domain.trim_start_matches("www.").to_ascii_lowercase();
+        add_unique(&mut self.domains, &domain)
+    }
+}
+
+fn add_unique(list: &mut Vec<String>, value: &str) -> bool {
+    if list.iter().any(|entry| entry == value) {
+        return false;
+    }
+    list.push(value.to_string());
+    true
+}
+
+/// Compiled form of [`MuteRules`] that is cheap to evaluate per item.
+#[derive(Debug, Clone, Default)]
+pub struct MuteFilter {
+    authors: Vec<String>,
+    domains: Vec<String>,
+    keywords: Vec<Regex>,
+}
+
+impl MuteFilter {
+    /// Invalid keyword regexes are skipped and reported in the error message.
+    pub fn new(rules: &MuteRules) -> (Self, Option<String>) {
+        let mut errors = Vec::new();
+        let keywords = rules
+            .keywords
+            .iter()
+            .filter_map(|keyword| match compile_keyword(keyword) {
+                Ok(regex) => Some(regex),
+                Err(error) => {
+                    errors.push(format!("Invalid mute keyword {keyword}: {error}"));
+                    None
+                }
+            })
+            .collect();
+
+        let filter = Self {
+            authors: rules.authors.clone(),
+            domains: rules
+                .domains
+                .iter()
+                .map(|domain| domain.to_ascii_lowercase())
+                .collect(),
+            keywords,
+        };
+        (filter, errors.into_iter().next())
+    }
+
+    pub fn is_author_muted(&self, author: &str) -> bool {
+        self.authors.iter().any(|muted| muted == author)
+    }
+
+    pub fn is_domain_muted(&self, url: &str) -> bool {
+        let Some(host) = url_domain(url) else {
+            return false;
+        };
+        self.domains
+            .iter()
+            .any(|domain| host_matches_domain(&host, domain))
+    }
+
+    pub fn is_title_muted(&self, title: &str) -> bool {
+        self.keywords.iter().any(|keyword| keyword.is_match(title))
+    }
+
+    pub fn is_post_muted(&self, author: &str, title: &str, url: &str) -> bool {
+        self.is_author_muted(author) || self.is_domain_muted(url) || self.is_title_muted(title)
+    }
+}
+
+/// Whether `host` is `domain` or one of its subdomains.
+pub fn host_matches_domain(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// Plain keywords match as case-insensitive substrings; `/pattern/` is a regex.
+pub fn compile_keyword(keyword: &str) -> Result<Regex, regex::Error> {
+    let pattern = match keyword
+        .strip_prefix('/')
+        .and_then(|rest| rest.strip_suffix('/'))
+    {
+        Some(pattern) if !pattern.is_empty() => pattern.to_string(),
+        _ => regex::escape(keyword),
+    };
+    RegexBuilder::new(&pattern).case_insensitive(true).build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_authors_domains_and_keywords() {
+        let mut rules = MuteRules {
+            keywords: vec!["crypto".to_string(), "/^show hn:/".to_string()],
+            ..MuteRules::default()
+        };
+        assert!(rules.mute_author("spammer"));
+        assert!(!rules.mute_author("spammer"));
+        assert!(rules.mute_domain("www.Example.com"));
+        let (filter, error) = MuteFilter::new(&rules);
+        assert_eq!(error, None);
+
+        assert!(filter.is_author_muted("spammer"));
+        assert!(!filter.is_author_muted("Spammer"));
+        assert!(filter.is_domain_muted("https://blog.example.com/post"));
+        assert!(filter.is_domain_muted("http://www.example.com"));
+        assert!(!filter.is_domain_muted("https://notexample.com"));
+        assert!(filter.is_title_muted("The Crypto winter"));
+        assert!(filter.is_title_muted("Show HN: A thing"));
+        assert!(!filter.is_title_muted("Ask HN: Show HN: etiquette?"));
+        assert!(!filter.is_post_muted("alice", "Rust 2.0", "https://rust-lang.org"));
+    }
+
+    #[test]
+    fn reports_invalid_regexes() {
+        let rules = MuteRules {
+            keywords: vec!["/(unclosed/".to_string(), "fine".to_string()],
+            ..MuteRules::default()
+        };
+        let (filter, error) = MuteFilter::new(&rules);
+
+        assert!(error.is_some_and(|error| error.contains("(unclosed")));
+        assert!(filter.is_title_muted("Fine print"));
+    }
+}
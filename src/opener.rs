@@ -0,0 +1,255 @@
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time;
+
+const URL_PLACEHOLDER: &str = "{url}";
+/// Placeholder used by the `$BROWSER` convention.
+const BROWSER_PLACEHOLDER: &str = "%s";
+
+/// Picks the opener template: the configured one first, then the first entry of
+/// `$BROWSER`. `None` falls back to the system default browser.
+pub fn resolve_template(configured: Option<&str>, browser_env: Option<&str>) -> Option<String> {
+    configured
+        .map(str::trim)
+        .filter(|template| !template.is_empty())
+        .or_else(|| {
+            browser_env?
+                .split(':')
+                .map(str::trim)
+                .find(|entry| !entry.is_empty())
+        })
+        .map(str::to_string)
+}
+
+/// Substitutes the URL for `{url}` or `%s`, appending it when the template has
+/// no placeholder. The URL is quoted for wherever the placeholder sits, so both
+/// `firefox {url}` and `firefox "{url}"` pass it through as one argument.
+pub fn build_command(template: &str, url: &str) -> String {
+    let Some(placeholder) = [URL_PLACEHOLDER, BROWSER_PLACEHOLDER]
+        .into_iter()
+        .find(|placeholder| template.contains(placeholder))
+    else {
+        return format!("{template} {}", shell_quote(url));
+    };
+
+    let mut command = String::new();
+    let mut quote: Option<char> = None;
+    let mut rest = template;
+    while let Some(ch) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix(placeholder) {
+            command.push_str(&quote_within(url, quote));
+            rest = after;
+            continue;
+        }
+
+        let mut len = ch.len_utf8();
+        match (quote, ch) {
+            // A backslash escapes the next character outside single quotes.
+            (None | Some('"'), '\\') => {
+                len += rest[len..].chars().next().map_or(0, char::len_utf8);
+            }
+            (None, '\'' | '"') => quote = Some(ch),
+            (Some(open), _) if ch == open => quote = None,
+            _ => {}
+        }
+        command.push_str(&rest[..len]);
+        rest = &rest[len..];
+    }
+    command
+}
+
+/// How long a launched opener gets to fail before it is left running on its
+/// own.
+const LAUNCH_GRACE: Duration = Duration::from_millis(500);
+/// Upper bound on a reader command such as `lynx -dump {url}`.
+const DUMP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Launches the opener for `url` detached from the terminal. Browsers keep
+/// running after this returns; only a command that fails straight away is
+/// reported.
+pub async fn open(url: String, template: Option<String>) -> Result<(), String> {
+    let Some(template) = template else {
+        return tokio::task::spawn_blocking(move || webbrowser::open(&url))
+            .await
+            .map_err(|error| error.to_string())?
+            .map_err(|error| format!("Failed to open browser: {error}"));
+    };
+
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(build_command(&template, &url))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|error| format!("Failed to run `{template}`: {error}"))?;
+
+    match time::timeout(LAUNCH_GRACE, child.wait()).await {
+        Ok(Ok(status)) if !status.success() => Err(format!("`{template}` exited with {status}")),
+        Ok(Err(error)) => Err(format!("Failed to run `{template}`: {error}")),
+        Ok(Ok(_)) | Err(_) => Ok(()),
+    }
+}
+
+/// Runs a reader command such as `lynx -dump {url}` and returns what it
+/// printed, giving up after [`DUMP_TIMEOUT`].
+pub async fn dump(url: String, template: String) -> Result<String, String> {
+    let command = Command::new("sh")
+        .arg("-c")
+        .arg(build_command(&template, &url))
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = time::timeout(DUMP_TIMEOUT, command)
+        .await
+        .map_err(|_| format!("`{template}` timed out"))?
+        .map_err(|error| format!("Failed to run `{template}`: {error}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(
+            match stderr.lines().map(str::trim).find(|line| !line.is_empty()) {
+                Some(reason) => format!("`{template}` exited with {}: {reason}", output.status),
+                None => format!("`{template}` exited with {}", output.status),
+            },
+        );
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stdout = stdout.trim_end();
+    if stdout.trim().is_empty() {
+        return Err(format!("`{template}` printed nothing"));
+    }
+    Ok(stdout.to_string())
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// `value` escaped to sit inside the given shell quotes, or quoted on its own.
+fn quote_within(value: &str, quote: Option<char>) -> String {
+    match quote {
+        None => shell_quote(value),
+        Some('\'') => value.replace('\'', r"'\''"),
+        Some(_) => {
+            let mut escaped = String::with_capacity(value.len());
+            for ch in value.chars() {
+                if matches!(ch, '"' | '\\' | '$' | '`') {
+                    escaped.push('\\');
+                }
+                escaped.push(ch);
+            }
+            escaped
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_template_wins_over_browser_env() {
+        assert_eq!(
+            resolve_template(Some("firefox --new-tab {url}"), Some("lynx")).as_deref(),
+            Some("firefox --new-tab {url}")
+        );
+        assert_eq!(
+            resolve_template(Some("  "), Some(":w3m %s:lynx")).as_deref(),
+            Some("w3m %s")
+        );
+        assert_eq!(resolve_template(None, None), None);
+    }
+
+    #[test]
+    fn builds_commands_with_quoted_urls() {
+        let url = "https://example.com/?q=it's&x=1";
+        let quoted = r"'https://example.com/?q=it'\''s&x=1'";
+
+        assert_eq!(
+            build_command("firefox --new-tab {url}", url),
+            format!("firefox --new-tab {quoted}")
+        );
+        assert_eq!(build_command("w3m %s", url), format!("w3m {quoted}"));
+        assert_eq!(
+            build_command("lynx -dump", url),
+            format!("lynx -dump {quoted}")
+        );
+    }
+
+    #[test]
+    fn quoted_placeholders_get_the_url_without_extra_quotes() {
+        let url = r#"https://example.com/?q=it's&x="$1""#;
+
+        assert_eq!(
+            build_command(r#"firefox "{url}""#, url),
+            r#"firefox "https://example.com/?q=it's&x=\"\$1\"""#
+        );
+        assert_eq!(
+            build_command("firefox '{url}'", url),
+            r#"firefox 'https://example.com/?q=it'\''s&x="$1"'"#
+        );
+        assert_eq!(
+            build_command(r#"sh -c "echo \"{url}\"" {url}"#, "https://a.example"),
+            r#"sh -c "echo \"https://a.example\"" 'https://a.example'"#
+        );
+    }
+
+    #[tokio::test]
+    async fn quoted_placeholders_reach_the_command_as_one_argument() {
+        let url = "https://example.com/?q=it's&x=1 2";
+
+        for template in [
+            "printf %s {url}",
+            r#"printf %s "{url}""#,
+            "printf %s '{url}'",
+        ] {
+            assert_eq!(
+                dump(url.to_string(), template.to_string()).await,
+                Ok(url.to_string()),
+                "{template}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn launches_openers_without_waiting_for_them() {
+        let started = std::time::Instant::now();
+        assert_eq!(
+            open(
+                "https://example.com".to_string(),
+                Some("sleep 5 #".to_string())
+            )
+            .await,
+            Ok(())
+        );
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        assert_eq!(
+            open("https://example.com".to_string(), Some("true".to_string())).await,
+            Ok(())
+        );
+        let error = open("https://example.com".to_string(), Some("false".to_string()))
+            .await
+            .unwrap_err();
+        assert!(error.contains("exited"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn reports_failing_reader_commands() {
+        let error = dump("https://example.com".to_string(), "true".to_string())
+            .await
+            .unwrap_err();
+        assert!(error.ends_with("printed nothing"), "{error}");
+
+        let error = dump(
+            "https://example.com".to_string(),
+            "sh -c 'echo no display >&2; exit 3'".to_string(),
+        )
+        .await
+        .unwrap_err();
+        assert!(error.ends_with(": no display"), "{error}");
+    }
+}
@@ -0,0 +1,322 @@
+use crate::{
+    app::FeedTab,
+    hn::parse_item_reference,
+    input::{
+        AlertsKeyAction, BookmarksKeyAction, CommentsKeyAction, GlobalKeyAction, PostsKeyAction,
+        ReaderKeyAction,
+    },
+};
+
+/// Rows the palette shows at once; longer match lists scroll with the selection.
+pub const MAX_VISIBLE_MATCHES: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteCommand {
+    Global(GlobalKeyAction),
+    Posts(PostsKeyAction),
+    Comments(CommentsKeyAction),
+    Reader(ReaderKeyAction),
+    Bookmarks(BookmarksKeyAction),
+    Alerts(AlertsKeyAction),
+    SwitchFeed(FeedTab),
+    OpenItem(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteEntry {
+    pub label: String,
+    pub command: PaletteCommand,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommandPalette {
+    query: String,
+    selected: usize,
+    entries: Vec<PaletteEntry>,
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        Self {
+            query: String::new(),
+            selected: 0,
+            entries: palette_entries(),
+        }
+    }
+
+    pub fn with_query(query: &str) -> Self {
+        let mut palette = Self::new();
+        palette.query = query.to_string();
+        palette
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn push_char(&mut self, ch: char) {
+        self.query.push(ch);
+        self.selected = 0;
+    }
+
+    pub fn pop_char(&mut self) {
+        self.query.pop();
+        self.selected = 0;
+    }
+
+    pub fn select_next(&mut self) {
+        let len = self.matches().len();
+        if len == 0 {
+            self.selected = 0;
+            return;
+        }
+        self.selected = (self.selected + 1) % len;
+    }
+
+    pub fn select_previous(&mut self) {
+        let len = self.matches().len();
+        if len == 0 {
+            self.selected = 0;
+            return;
+        }
+        self.selected = (self.selected + len - 1) % len;
+    }
+
+    pub fn matches(&self) -> Vec<PaletteEntry> {
+        let query = self.query.trim().trim_start_matches(':').trim();
+        let mut matches: Vec<PaletteEntry> = Vec::new();
+        if let Some(entry) = parse_argument_command(query) {
+            matches.push(entry);
+        }
+
+        let mut scored: Vec<(i64, usize, &PaletteEntry)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                fuzzy_score(query, &entry.label).map(|score| (score, index, entry))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        matches.extend(scored.into_iter().map(|(_, _, entry)| entry.clone()));
+        matches
+    }
+
+    pub fn selected_command(&self) -> Option<PaletteCommand> {
+        self.matches().get(self.selected).map(|entry| entry.command)
+    }
+}
+
+fn palette_entries() -> Vec<PaletteEntry> {
+    let mut entries = Vec::new();
+
+    let global = GlobalKeyAction::ALL.iter().map(|action| {
+        (
+            format!("Global: {}", action.label()),
+            PaletteCommand::Global(*action),
+        )
+    });
+    let posts = PostsKeyAction::ALL.iter().map(|action| {
+        (
+            format!("Posts: {}", action.label()),
+            PaletteCommand::Posts(*action),
+        )
+    });
+    let comments = CommentsKeyAction::ALL.iter().map(|action| {
+        (
+            format!("Comments: {}", action.label()),
+            PaletteCommand::Comments(*action),
+        )
+    });
+    let reader = ReaderKeyAction::ALL.iter().map(|action| {
+        (
+            format!("Reader: {}", action.label()),
+            PaletteCommand::Reader(*action),
+        )
+    });
+    let bookmarks = BookmarksKeyAction::ALL.iter().map(|action| {
+        (
+            format!("Bookmarks: {}", action.label()),
+            PaletteCommand::Bookmarks(*action),
+        )
+    });
+    let alerts = AlertsKeyAction::ALL.iter().map(|action| {
+        (
+            format!("Alerts: {}", action.label()),
+            PaletteCommand::Alerts(*action),
+        )
+    });
+    let feeds = FeedTab::ALL.iter().map(|feed| {
+        (
+            format!("Feed: {}", feed.label()),
+            PaletteCommand::SwitchFeed(*feed),
+        )
+    });
+
+    for (label, command) in global
+        .chain(posts)
+        .chain(comments)
+        .chain(reader)
+        .chain(bookmarks)
+        .chain(alerts)
+        .chain(feeds)
+    {
+        entries.push(PaletteEntry { label, command });
+    }
+
+    entries
+}
+
+/// Parses commands that carry an argument, such as `open 12345`, `open <hn url>` or
+/// `feed ask`.
+fn parse_argument_command(query: &str) -> Option<PaletteEntry> {
+    let (name, argument) = query.split_once(char::is_whitespace)?;
+    let argument = argument.trim();
+    if argument.is_empty() {
+        return None;
+    }
+
+    match name.to_ascii_lowercase().as_str() {
+        "open" => {
+            let id = parse_item_reference(argument)?;
+            Some(PaletteEntry {
+                label: format!("Open item {id}"),
+                command: PaletteCommand::OpenItem(id),
+            })
+        }
+        "feed" => {
+            let feed = FeedTab::ALL
+                .iter()
+                .find(|feed| feed.label().eq_ignore_ascii_case(argument))?;
+            Some(PaletteEntry {
+                label: format!("Switch to {} feed", feed.label()),
+                command: PaletteCommand::SwitchFeed(*feed),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Scores `candidate` against `query` as a case-insensitive subsequence match,
+/// rewarding consecutive characters and matches at the start of words.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+    let mut score = 0i64;
+    let mut search_from = 0usize;
+    let mut previous_match: Option<usize> = None;
+
+    for query_char in query.chars().filter(|ch| !ch.is_whitespace()) {
+        let query_char = query_char.to_ascii_lowercase();
+        let offset = candidate_chars[search_from..]
+            .iter()
+            .position(|ch| ch.to_ascii_lowercase() == query_char)?;
+        let index = search_from + offset;
+
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += 5;
+        }
+        let at_word_start = index == 0
+            || candidate_chars
+                .get(index - 1)
+                .is_some_and(|ch| !ch.is_alphanumeric());
+        if at_word_start {
+            score += 8;
+        }
+        score -= offset as i64;
+
+        previous_match = Some(index);
+        search_from = index + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_score_requires_subsequence_and_prefers_word_starts() {
+        assert!(fuzzy_score("xyz", "Open comments").is_none());
+
+        let word_start = fuzzy_score("oc", "Posts: Open comments").unwrap();
+        let scattered = fuzzy_score("oc", "Posts: Bookmark selected").unwrap_or(i64::MIN);
+        assert!(word_start > scattered);
+    }
+
+    #[test]
+    fn matches_are_ranked_by_score() {
+        let palette = CommandPalette::with_query("quit");
+        let matches = palette.matches();
+
+        assert_eq!(
+            matches[0].command,
+            PaletteCommand::Global(GlobalKeyAction::Quit)
+        );
+    }
+
+    #[test]
+    fn argument_commands_are_parsed_first() {
+        let palette = CommandPalette::with_query(":open 12345");
+        assert_eq!(
+            palette.selected_command(),
+            Some(PaletteCommand::OpenItem(12345))
+        );
+
+        let palette = CommandPalette::with_query("open https://news.ycombinator.com/item?id=99");
+        assert_eq!(
+            palette.selected_command(),
+            Some(PaletteCommand::OpenItem(99))
+        );
+
+        let palette = CommandPalette::with_query("feed ASK");
+        assert_eq!(
+            palette.selected_command(),
+            Some(PaletteCommand::SwitchFeed(FeedTab::Ask))
+        );
+
+        let palette = CommandPalette::with_query("open nope");
+        assert_ne!(
+            palette.selected_command(),
+            Some(PaletteCommand::OpenItem(0))
+        );
+    }
+
+    #[test]
+    fn selection_wraps_around_matches() {
+        let mut palette = CommandPalette::with_query("feed");
+        let len = palette.matches().len();
+        assert!(len > 1);
+
+        palette.select_previous();
+        assert_eq!(palette.selected(), len - 1);
+        palette.select_next();
+        assert_eq!(palette.selected(), 0);
+    }
+
+    #[test]
+    fn every_command_is_reachable_from_an_empty_query() {
+        let mut palette = CommandPalette::new();
+        let len = palette.matches().len();
+        assert_eq!(len, palette_entries().len());
+        assert!(len > MAX_VISIBLE_MATCHES);
+
+        for _ in 0..MAX_VISIBLE_MATCHES {
+            palette.select_next();
+        }
+        assert_eq!(
+            palette.selected_command(),
+            Some(palette.matches()[MAX_VISIBLE_MATCHES].command)
+        );
+    }
+}
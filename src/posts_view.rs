@@ -0,0 +1,211 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+
+const MIN_POINTS_STEPS: [u64; 5] = [10, 50, 100, 250, 500];
+const MIN_COMMENTS_STEPS: [u64; 4] = [10, 50, 100, 250];
+const MAX_AGE_HOURS_STEPS: [u64; 4] = [1, 6, 24, 72];
+
+/// Order of the posts list. `Rank` keeps the order the feed was fetched in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PostSort {
+    #[default]
+    Rank,
+    Points,
+    Comments,
+    Age,
+    PointsPerHour,
+}
+
+impl PostSort {
+    pub const ALL: [Self; 5] = [
+        Self::Rank,
+        Self::Points,
+        Self::Comments,
+        Self::Age,
+        Self::PointsPerHour,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Rank => "rank",
+            Self::Points => "points",
+            Self::Comments => "comments",
+            Self::Age => "newest",
+            Self::PointsPerHour => "points/hour",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|sort| *sort == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// The numbers a post is sorted and filtered by.
+#[derive(Debug, Clone, Copy)]
+pub struct PostStats {
+    pub points: u64,
+    pub comments: u64,
+    pub published_at: u64,
+    pub is_job: bool,
+}
+
+impl PostStats {
+    fn age_secs(&self, now: u64) -> u64 {
+        now.saturating_sub(self.published_at)
+    }
+
+    fn points_per_hour(&self, now: u64) -> f64 {
+        self.points as f64 * 3600.0 / self.age_secs(now).max(60) as f64
+    }
+}
+
+/// Sort mode and filters of one feed's posts list, saved per feed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PostsView {
+    pub sort: PostSort,
+    pub min_points: Option<u64>,
+    pub min_comments: Option<u64>,
+    pub max_age_hours: Option<u64>,
+    pub hide_jobs: bool,
+}
+
+impl PostsView {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn matches(&self, stats: &PostStats, now: u64) -> bool {
+        self.min_points.is_none_or(|min| stats.points >= min)
+            && self.min_comments.is_none_or(|min| stats.comments >= min)
+            && self
+                .max_age_hours
+                .is_none_or(|hours| stats.age_secs(now) <= hours * 3600)
+            && !(self.hide_jobs && stats.is_job)
+    }
+
+    /// Sorts `posts` in place; ties keep their feed order.
+    pub fn sort<T>(&self, posts: &mut [T], stats: impl Fn(&T) -> PostStats, now: u64) {
+        match self.sort {
+            PostSort::Rank => {}
+            PostSort::Points => posts.sort_by_key(|post| Reverse(stats(post).points)),
+            PostSort::Comments => posts.sort_by_key(|post| Reverse(stats(post).comments)),
+            PostSort::Age => posts.sort_by_key(|post| Reverse(stats(post).published_at)),
+            PostSort::PointsPerHour => posts.sort_by(|a, b| {
+                stats(b)
+                    .points_per_hour(now)
+                    .total_cmp(&stats(a).points_per_hour(now))
+            }),
+        }
+    }
+
+    pub fn cycle_min_points(&mut self) {
+        self.min_points = next_step(&MIN_POINTS_STEPS, self.min_points);
+    }
+
+    pub fn cycle_min_comments(&mut self) {
+        self.min_comments = next_step(&MIN_COMMENTS_STEPS, self.min_comments);
+    }
+
+    pub fn cycle_max_age(&mut self) {
+        self.max_age_hours = next_step(&MAX_AGE_HOURS_STEPS, self.max_age_hours);
+    }
+
+    /// Short description for the pane title, e.g. `by points, ≥100 points, <24h`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.sort != PostSort::Rank {
+            parts.push(format!("by {}", self.sort.label()));
+        }
+        if let Some(min) = self.min_points {
+            parts.push(format!("≥{min} points"));
+        }
+        if let Some(min) = self.min_comments {
+            parts.push(format!("≥{min} comments"));
+        }
+        if let Some(hours) = self.max_age_hours {
+            parts.push(format!("<{hours}h"));
+        }
+        if self.hide_jobs {
+            parts.push("no jobs".to_string());
+        }
+        parts.join(", ")
+    }
+}
+
+/// The step after `current`, wrapping back to off after the last one.
+fn next_step(steps: &[u64], current: Option<u64>) -> Option<u64> {
+    match current {
+        None => steps.first().copied(),
+        Some(current) => steps.iter().copied().find(|step| *step > current),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(points: u64, comments: u64, published_at: u64, is_job: bool) -> PostStats {
+        PostStats {
+            points,
+            comments,
+            published_at,
+            is_job,
+        }
+    }
+
+    #[test]
+    fn filters_combine() {
+        let now = 100_000;
+        let view = PostsView {
+            min_points: Some(50),
+            max_age_hours: Some(6),
+            hide_jobs: true,
+            ..PostsView::default()
+        };
+
+        assert!(view.matches(&stats(50, 0, now - 3600, false), now));
+        assert!(!view.matches(&stats(49, 0, now - 3600, false), now));
+        assert!(!view.matches(&stats(80, 0, now - 7 * 3600, false), now));
+        assert!(!view.matches(&stats(80, 0, now, true), now));
+        assert_eq!(view.summary(), "≥50 points, <6h, no jobs");
+    }
+
+    #[test]
+    fn sorts_keep_feed_order_for_ties() {
+        let now = 10 * 3600;
+        let posts = vec![
+            ("a", stats(10, 5, 0, false)),
+            ("b", stats(30, 5, 9 * 3600, false)),
+            ("c", stats(30, 9, 8 * 3600, false)),
+        ];
+        let sorted = |sort| {
+            let mut posts = posts.clone();
+            PostsView {
+                sort,
+                ..PostsView::default()
+            }
+            .sort(&mut posts, |(_, stats)| *stats, now);
+            posts.iter().map(|(name, _)| *name).collect::<String>()
+        };
+
+        assert_eq!(sorted(PostSort::Rank), "abc");
+        assert_eq!(sorted(PostSort::Points), "bca");
+        assert_eq!(sorted(PostSort::Comments), "cab");
+        assert_eq!(sorted(PostSort::Age), "bca");
+        assert_eq!(sorted(PostSort::PointsPerHour), "bca");
+    }
+
+    #[test]
+    fn filter_steps_cycle_back_to_off() {
+        let mut view = PostsView::default();
+        for expected in MIN_POINTS_STEPS {
+            view.cycle_min_points();
+            assert_eq!(view.min_points, Some(expected));
+        }
+        view.cycle_min_points();
+        assert_eq!(view.min_points, None);
+    }
+}
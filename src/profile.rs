@@ -0,0 +1,172 @@
+use crate::{
+    hn::{Item, UserProfile},
+    html::{blocks_to_plain_text, decode_html_entities, parse_hn_html},
+};
+
+const SNIPPET_CHARS: usize = 80;
+
+/// State of the user profile popup.
+#[derive(Debug, Clone)]
+pub struct ProfileView {
+    username: String,
+    profile: Option<UserProfile>,
+    error: Option<String>,
+    selected: usize,
+}
+
+impl ProfileView {
+    pub fn loading(username: &str) -> Self {
+        Self {
+            username: username.to_string(),
+            profile: None,
+            error: None,
+            selected: 0,
+        }
+    }
+
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    pub fn profile(&self) -> Option<&UserProfile> {
+        self.profile.as_ref()
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.profile.is_none() && self.error.is_none()
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn set_result(&mut self, result: Result<Option<UserProfile>, String>) {
+        match result {
+            Ok(Some(profile)) => self.profile = Some(profile),
+            Ok(None) => self.error = Some(format!("No user named {}", self.username)),
+            Err(error) => self.error = Some(error),
+        }
+        self.selected = 0;
+    }
+
+    fn submissions(&self) -> &[Item] {
+        self.profile
+            .as_ref()
+            .map_or(&[], |profile| profile.submissions.as_slice())
+    }
+
+    pub fn select_next(&mut self) {
+        let len = self.submissions().len();
+        if len > 0 {
+            self.selected = (self.selected + 1).min(len - 1);
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn selected_submission(&self) -> Option<&Item> {
+        self.submissions().get(self.selected)
+    }
+}
+
+/// The `about` text with HN's HTML reduced to plain text.
+pub fn about_text(profile: &UserProfile) -> String {
+    profile
+        .user
+        .about
+        .as_deref()
+        .map(|about| blocks_to_plain_text(&parse_hn_html(about)))
+        .unwrap_or_default()
+}
+
+/// One line per submission: the title for stories, a text snippet otherwise.
+pub fn submission_label(item: &Item) -> String {
+    if let Some(title) = item.title.as_deref() {
+        return decode_html_entities(title);
+    }
+
+    let text = item
+        .text
+        .as_deref()
+        .map(|text| blocks_to_plain_text(&parse_hn_html(text)))
+        .unwrap_or_default();
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let snippet = if text.chars().count() > SNIPPET_CHARS {
+        format!(
+            "{}...",
+            text.chars().take(SNIPPET_CHARS).collect::<String>()
+        )
+    } else {
+        text
+    };
+    format!("Comment: {snippet}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hn::User;
+
+    fn item(id: u64, title: Option<&str>, text: Option<&str>) -> Item {
+        Item {
+            id,
+            title: title.map(str::to_string),
+            url: None,
+            score: None,
+            descendants: None,
+            by: Some("pg".to_string()),
+            time: None,
+            text: text.map(str::to_string),
+            kids: None,
+            parent: None,
+            parts: None,
+            kind: Some(if title.is_some() { "story" } else { "comment" }.to_string()),
+            dead: false,
+            deleted: false,
+        }
+    }
+
+    #[test]
+    fn labels_cleans_about_and_clamps_selection() {
+        let profile = UserProfile {
+            user: User {
+                id: "pg".to_string(),
+                karma: 1,
+                created: 0,
+                about: Some(
+                    "Bug fixer.<p>See <a href=\"https://x.example\">https://x.example</a>"
+                        .to_string(),
+                ),
+                submitted: vec![1, 2],
+            },
+            submissions: vec![
+                item(1, Some("Q&amp;A"), None),
+                item(2, None, Some("A <i>short</i> reply")),
+            ],
+        };
+
+        assert_eq!(about_text(&profile), "Bug fixer.\nSee https://x.example");
+        assert_eq!(submission_label(&profile.submissions[0]), "Q&A");
+        assert_eq!(
+            submission_label(&profile.submissions[1]),
+            "Comment: A short reply"
+        );
+
+        let mut view = ProfileView::loading("pg");
+        assert!(view.is_loading());
+        view.set_result(Ok(Some(profile)));
+        view.select_next();
+        view.select_next();
+        assert_eq!(view.selected_submission().map(|item| item.id), Some(2));
+
+        let mut missing = ProfileView::loading("nobody");
+        missing.set_result(Ok(None));
+        assert_eq!(missing.error(), Some("No user named nobody"));
+    }
+}
@@ -0,0 +1,659 @@
+use crate::html::decode_html_entities;
+use reqwest::header::CONTENT_TYPE;
+use std::time::Duration;
+
+const READER_TIMEOUT_SECS: u64 = 15;
+const READER_USER_AGENT: &str = "Mozilla/5.0 (compatible; lazynews reader)";
+/// Pages larger than this are not downloaded for reader mode.
+const MAX_ARTICLE_BYTES: usize = 4 * 1024 * 1024;
+/// Paragraphs shorter than this are not counted when scoring containers.
+const MIN_SCORED_PARAGRAPH_LEN: usize = 25;
+const CLASS_WEIGHT: i64 = 25;
+const POSITIVE_CLASS_HINTS: [&str; 10] = [
+    "article", "body", "content", "entry", "main", "page", "post", "text", "blog", "story",
+];
+const NEGATIVE_CLASS_HINTS: [&str; 20] = [
+    "comment",
+    "contact",
+    "foot",
+    "masthead",
+    "meta",
+    "promo",
+    "related",
+    "share",
+    "sidebar",
+    "sponsor",
+    "widget",
+    "nav",
+    "menu",
+    "banner",
+    "advert",
+    "cookie",
+    "subscribe",
+    "newsletter",
+    "social",
+    "popup",
+];
+/// Elements whose contents never belong to the article.
+const SKIPPED_TAGS: [&str; 14] = [
+    "script", "style", "noscript", "svg", "nav", "header", "footer", "aside", "form", "iframe",
+    "button", "template", "select", "head",
+];
+const RAW_TEXT_TAGS: [&str; 4] = ["script", "style", "title", "textarea"];
+const VOID_TAGS: [&str; 12] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "wbr",
+];
+const CONTAINER_TAGS: [&str; 12] = [
+    "body",
+    "div",
+    "article",
+    "main",
+    "section",
+    "td",
+    "table",
+    "tbody",
+    "tr",
+    "ul",
+    "ol",
+    "blockquote",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArticleBlock {
+    Heading(String),
+    Paragraph(String),
+    Quote(String),
+    ListItem(String),
+    Preformatted(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Article {
+    pub title: Option<String>,
+    pub blocks: Vec<ArticleBlock>,
+}
+
+#[derive(Clone)]
+pub struct ReaderClient {
+    client: reqwest::Client,
+}
+
+impl ReaderClient {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(READER_TIMEOUT_SECS))
+            .user_agent(READER_USER_AGENT)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self { client }
+    }
+
+    /// Downloads `url` and extracts its readable content. Non-HTML responses
+    /// and pages without any readable text are reported as errors.
+    pub async fn fetch_article(&self, url: &str) -> Result<Article, String> {
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| e.to_string())?;
+
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if !content_type.contains("html") {
+            let content_type = if content_type.is_empty() {
+                "unknown"
+            } else {
+                content_type.split(';').next().unwrap_or_default().trim()
+            };
+            return Err(format!(
+                "Reader mode only supports HTML pages (content type: {content_type})"
+            ));
+        }
+
+        let too_large = || "Page is too large for reader mode".to_string();
+        if response
+            .content_length()
+            .is_some_and(|length| length > MAX_ARTICLE_BYTES as u64)
+        {
+            return Err(too_large());
+        }
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+            if body.len() + chunk.len() > MAX_ARTICLE_BYTES {
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        let html = String::from_utf8_lossy(&body);
+        let article = extract_article(&html);
+        if article.blocks.is_empty() {
+            return Err("No readable content found on the page".to_string());
+        }
+        Ok(article)
+    }
+}
+
+/// Extracts the main content of an HTML page, readability style: text blocks are
+/// collected per container, containers are scored by the paragraphs they hold
+/// (with class/id hints and link density taken into account), and only the
+/// blocks under the best scoring container are kept.
+pub fn extract_article(html: &str) -> Article {
+    let mut extractor = Extractor::default();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            extractor.text(rest);
+            break;
+        };
+        extractor.text(&rest[..tag_start]);
+        rest = &rest[tag_start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment
+                .find("-->")
+                .map_or("", |end| &comment[end + "-->".len()..]);
+            continue;
+        }
+
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..tag_end];
+        rest = &rest[tag_end + 1..];
+
+        if let Some(name) = extractor.tag(tag) {
+            // Raw text elements end at the matching close tag, whatever they contain.
+            let close = format!("</{name}");
+            let end = find_ignore_ascii_case(rest, &close).unwrap_or(rest.len());
+            if name == "title" && extractor.title.is_none() {
+                let title = collapse_whitespace(&decode_html_entities(&rest[..end]));
+                extractor.title = Some(title).filter(|title| !title.is_empty());
+            }
+            rest = &rest[end..];
+            rest = rest
+                .find('>')
+                .map_or("", |close_end| &rest[close_end + 1..]);
+        }
+    }
+
+    extractor.finish()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    Heading,
+    Paragraph,
+    ListItem,
+    Preformatted,
+}
+
+struct RawBlock {
+    block: ArticleBlock,
+    node: Option<usize>,
+    link_density: f64,
+}
+
+struct Node {
+    parent: Option<usize>,
+    weight: i64,
+    score: f64,
+}
+
+struct OpenElement {
+    name: String,
+    node: Option<usize>,
+    skipped: bool,
+}
+
+#[derive(Default)]
+struct Extractor {
+    title: Option<String>,
+    nodes: Vec<Node>,
+    stack: Vec<OpenElement>,
+    skipping: usize,
+    quote_depth: usize,
+    link_depth: usize,
+    current_kind: Option<BlockKind>,
+    current_text: String,
+    current_link_len: usize,
+    blocks: Vec<RawBlock>,
+}
+
+impl Extractor {
+    /// Handles a tag, returning its name when it opens a raw text element whose
+    /// contents must be skipped verbatim.
+    fn tag(&mut self, tag: &str) -> Option<String> {
+        let tag = tag.trim();
+        if tag.starts_with('!') || tag.starts_with('?') {
+            return None;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            self.close(&tag_name(name));
+            return None;
+        }
+
+        let name = tag_name(tag);
+        if name.is_empty() {
+            return None;
+        }
+        let self_closing = tag.ends_with('/') || VOID_TAGS.contains(&name.as_str());
+
+        if name == "br" {
+            if self.current_kind.is_some() {
+                self.current_text.push('\n');
+            }
+            return None;
+        }
+        if self_closing {
+            return None;
+        }
+        if RAW_TEXT_TAGS.contains(&name.as_str()) {
+            return Some(name);
+        }
+
+        let skipped = SKIPPED_TAGS.contains(&name.as_str()) || is_hidden(tag);
+        if skipped {
+            self.skipping += 1;
+        }
+
+        let block_kind = match name.as_str() {
+            "p" => Some(BlockKind::Paragraph),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => Some(BlockKind::Heading),
+            "li" => Some(BlockKind::ListItem),
+            "pre" => Some(BlockKind::Preformatted),
+            _ => None,
+        };
+        if block_kind.is_some() || CONTAINER_TAGS.contains(&name.as_str()) {
+            // Block boundaries end any running text, including unclosed `<p>`s.
+            if self.current_kind != Some(BlockKind::Preformatted) {
+                self.flush_block();
+            }
+        }
+        if let Some(kind) = block_kind
+            && self.current_kind != Some(BlockKind::Preformatted)
+        {
+            self.current_kind = Some(kind);
+        }
+
+        let node = CONTAINER_TAGS.contains(&name.as_str()).then(|| {
+            self.nodes.push(Node {
+                parent: self.current_node(),
+                weight: element_weight(&name, tag),
+                score: 0.0,
+            });
+            self.nodes.len() - 1
+        });
+        match name.as_str() {
+            "blockquote" => self.quote_depth += 1,
+            "a" => self.link_depth += 1,
+            _ => {}
+        }
+
+        self.stack.push(OpenElement {
+            name,
+            node,
+            skipped,
+        });
+        None
+    }
+
+    fn close(&mut self, name: &str) {
+        let Some(position) = self.stack.iter().rposition(|open| open.name == name) else {
+            return;
+        };
+
+        let closes_block = matches!(
+            name,
+            "p" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "li" | "pre"
+        ) || CONTAINER_TAGS.contains(&name);
+        if closes_block && (name == "pre" || self.current_kind != Some(BlockKind::Preformatted)) {
+            self.flush_block();
+        }
+
+        for open in self.stack.drain(position..).collect::<Vec<_>>() {
+            if open.skipped {
+                self.skipping -= 1;
+            }
+            match open.name.as_str() {
+                "blockquote" => self.quote_depth = self.quote_depth.saturating_sub(1),
+                "a" => self.link_depth = self.link_depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+    }
+
+    fn text(&mut self, raw: &str) {
+        if self.skipping > 0 || raw.is_empty() {
+            return;
+        }
+
+        let decoded = decode_html_entities(raw);
+        if self.current_kind.is_none() {
+            if decoded.trim().is_empty() {
+                return;
+            }
+            // Loose text directly inside a container becomes its own paragraph.
+            self.current_kind = Some(BlockKind::Paragraph);
+        }
+        if self.link_depth > 0 {
+            self.current_link_len += decoded.trim().chars().count();
+        }
+        self.current_text.push_str(&decoded);
+    }
+
+    fn current_node(&self) -> Option<usize> {
+        self.stack.iter().rev().find_map(|open| open.node)
+    }
+
+    fn flush_block(&mut self) {
+        let Some(kind) = self.current_kind.take() else {
+            return;
+        };
+        let raw = std::mem::take(&mut self.current_text);
+        let link_len = std::mem::take(&mut self.current_link_len);
+
+        let text = if kind == BlockKind::Preformatted {
+            raw.trim_matches('\n').trim_end().to_string()
+        } else {
+            raw.lines()
+                .map(collapse_whitespace)
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        if text.trim().is_empty() {
+            return;
+        }
+
+        let len = text.chars().count();
+        let block = match kind {
+            BlockKind::Heading => ArticleBlock::Heading(text),
+            BlockKind::ListItem => ArticleBlock::ListItem(text),
+            BlockKind::Preformatted => ArticleBlock::Preformatted(text),
+            BlockKind::Paragraph if self.quote_depth > 0 => ArticleBlock::Quote(text),
+            BlockKind::Paragraph => ArticleBlock::Paragraph(text),
+        };
+        self.blocks.push(RawBlock {
+            block,
+            node: self.current_node(),
+            link_density: link_len as f64 / len.max(1) as f64,
+        });
+    }
+
+    fn finish(mut self) -> Article {
+        self.flush_block();
+
+        for raw in &self.blocks {
+            let (ArticleBlock::Paragraph(text)
+            | ArticleBlock::Quote(text)
+            | ArticleBlock::Preformatted(text)) = &raw.block
+            else {
+                continue;
+            };
+            let len = text.chars().count();
+            if len < MIN_SCORED_PARAGRAPH_LEN {
+                continue;
+            }
+
+            let score = (1.0 + text.matches(',').count() as f64 + (len / 100).min(3) as f64)
+                * (1.0 - raw.link_density);
+            if let Some(parent) = raw.node {
+                self.nodes[parent].score += score;
+                if let Some(grandparent) = self.nodes[parent].parent {
+                    self.nodes[grandparent].score += score / 2.0;
+                }
+            }
+        }
+
+        let best = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.score > 0.0)
+            .map(|(index, node)| (index, node.score + node.weight as f64))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index);
+
+        let nodes = &self.nodes;
+        let blocks = self
+            .blocks
+            .into_iter()
+            .filter(|raw| best.is_none_or(|best| is_within(nodes, raw.node, best)))
+            .filter(|raw| {
+                matches!(raw.block, ArticleBlock::Preformatted(_)) || raw.link_density <= 0.5
+            })
+            .map(|raw| raw.block)
+            .collect();
+
+        Article {
+            title: self.title,
+            blocks,
+        }
+    }
+}
+
+fn is_within(nodes: &[Node], mut node: Option<usize>, ancestor: usize) -> bool {
+    while let Some(index) = node {
+        if index == ancestor {
+            return true;
+        }
+        node = nodes[index].parent;
+    }
+    false
+}
+
+fn tag_name(tag: &str) -> String {
+    tag.trim_start()
+        .split(|ch: char| ch.is_whitespace() || ch == '/' || ch == '>')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Scores an element by its tag and the hints in its `class` and `id`.
+fn element_weight(name: &str, tag: &str) -> i64 {
+    let mut weight = match name {
+        "article" => 10,
+        "main" => 5,
+        _ => 0,
+    };
+
+    let hints = ["class", "id"]
+        .iter()
+        .filter_map(|attribute| attribute_value(tag, attribute))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_ascii_lowercase();
+    if POSITIVE_CLASS_HINTS.iter().any(|hint| hints.contains(hint)) {
+        weight += CLASS_WEIGHT;
+    }
+    if NEGATIVE_CLASS_HINTS.iter().any(|hint| hints.contains(hint)) {
+        weight -= CLASS_WEIGHT;
+    }
+    weight
+}
+
+fn is_hidden(tag: &str) -> bool {
+    let lower = tag.to_ascii_lowercase();
+    lower.split_whitespace().any(|part| part == "hidden")
+        || attribute_value(tag, "aria-hidden").is_some_and(|value| value == "true")
+        || attribute_value(tag, "style").is_some_and(|style| {
+            style
+                .to_ascii_lowercase()
+                .replace(' ', "")
+                .contains("display:none")
+        })
+}
+
+fn attribute_value(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search_from = 0;
+    while let Some(offset) = lower[search_from..].find(name) {
+        let start = search_from + offset;
+        search_from = start + name.len();
+
+        let preceded_by_space = lower[..start]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_whitespace);
+        let rest = lower[search_from..].trim_start();
+        if !preceded_by_space || !rest.starts_with('=') {
+            continue;
+        }
+
+        let value_start = tag.len() - rest.len() + 1;
+        let value = tag[value_start..].trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+            _ => value.split_whitespace().next().unwrap_or_default(),
+        };
+        return Some(value.to_string());
+    }
+    None
+}
+
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    const ARTICLE_HTML: &str = r#"<!DOCTYPE html>
+<html>
+<head><title>A &amp; B | Example</title><style>p { color: red; }</style></head>
+<body>
+  <header><p>Site navigation that is long enough to count, with commas, and more</p></header>
+  <div class="sidebar">
+    <p>Related: a long list of other stories, widgets, and links to nowhere at all</p>
+  </div>
+  <article class="post-content">
+    <h1>The headline</h1>
+    <p>First paragraph of the story, which has plenty of words, commas, and detail.</p>
+    <p>Second paragraph<br>with a line break and an <a href="/x">inline link</a> in it.</p>
+    <blockquote><p>A quoted passage that is also part of the article body text.</p></blockquote>
+    <ul><li>One point</li><li>Another point</li></ul>
+    <pre><code>fn main() {
+    println!("hi");
+}</code></pre>
+    <script>document.write("<p>injected</p>")</script>
+  </article>
+  <footer><p>Copyright notice, terms of service, privacy policy, and so on</p></footer>
+</body>
+</html>"#;
+
+    #[test]
+    fn extracts_the_main_content_block() {
+        let article = extract_article(ARTICLE_HTML);
+
+        assert_eq!(article.title.as_deref(), Some("A & B | Example"));
+        assert_eq!(
+            article.blocks,
+            vec![
+                ArticleBlock::Heading("The headline".to_string()),
+                ArticleBlock::Paragraph(
+                    "First paragraph of the story, which has plenty of words, commas, and detail."
+                        .to_string()
+                ),
+                ArticleBlock::Paragraph(
+                    "Second paragraph\nwith a line break and an inline link in it.".to_string()
+                ),
+                ArticleBlock::Quote(
+                    "A quoted passage that is also part of the article body text.".to_string()
+                ),
+                ArticleBlock::ListItem("One point".to_string()),
+                ArticleBlock::ListItem("Another point".to_string()),
+                ArticleBlock::Preformatted("fn main() {\n    println!(\"hi\");\n}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unclosed_paragraphs_and_loose_text_become_blocks() {
+        let article = extract_article("<body><div>Loose text in a div<p>One<p>Two</div></body>");
+
+        assert_eq!(
+            article.blocks,
+            vec![
+                ArticleBlock::Paragraph("Loose text in a div".to_string()),
+                ArticleBlock::Paragraph("One".to_string()),
+                ArticleBlock::Paragraph("Two".to_string()),
+            ]
+        );
+    }
+
+    /// Serves a single canned HTTP response per connection on a local port.
+    async fn fixture_server(content_type: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 1024];
+                let _ = socket.read(&mut request).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{address}/article")
+    }
+
+    #[tokio::test]
+    async fn fetches_and_extracts_html_from_a_server() {
+        let url = fixture_server("text/html; charset=utf-8", ARTICLE_HTML).await;
+
+        let article = ReaderClient::new().fetch_article(&url).await.unwrap();
+
+        assert_eq!(
+            article.blocks.first(),
+            Some(&ArticleBlock::Heading("The headline".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_pages_over_the_size_limit() {
+        let body = "a".repeat(MAX_ARTICLE_BYTES + 1).leak();
+        let url = fixture_server("text/html", body).await;
+
+        assert_eq!(
+            ReaderClient::new().fetch_article(&url).await,
+            Err("Page is too large for reader mode".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_non_html_content() {
+        let url = fixture_server("application/pdf", "%PDF-1.4").await;
+
+        let error = ReaderClient::new().fetch_article(&url).await.unwrap_err();
+
+        assert_eq!(
+            error,
+            "Reader mode only supports HTML pages (content type: application/pdf)"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{mute::MuteRules, posts_view::PostsView};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const STATE_FILE_NAME: &str = "state.toml";
+
+/// Data the app writes back between sessions, kept apart from the hand-edited
+/// config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedState {
+    pub followed_users: Vec<String>,
+    pub mutes: MuteRules,
+    /// Sort and filters of each feed's posts list, keyed by feed label.
+    pub posts_views: BTreeMap<String, PostsView>,
+}
+
+impl SavedState {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents)
+                .map_err(|error| format!("Invalid state file {}: {error}", path.display())),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(format!("Failed to read {}: {error}", path.display())),
+        }
+    }
+
+    /// Writes to a temporary file first so a crash never leaves a truncated file.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = toml::to_string(self).map_err(|error| error.to_string())?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|error| format!("Failed to create {}: {error}", dir.display()))?;
+        }
+
+        let temp_path = path.with_extension("toml.tmp");
+        fs::write(&temp_path, contents)
+            .and_then(|()| fs::rename(&temp_path, path))
+            .map_err(|error| format!("Failed to write {}: {error}", path.display()))
+    }
+
+    pub fn is_followed(&self, username: &str) -> bool {
+        self.followed_users.iter().any(|user| user == username)
+    }
+
+    /// Follows or unfollows `username`, returning whether it is now followed.
+    pub fn toggle_follow(&mut self, username: &str) -> bool {
+        if self.is_followed(username) {
+            self.followed_users.retain(|user| user != username);
+            false
+        } else {
+            self.followed_users.push(username.to_string());
+            true
+        }
+    }
+}
+
+pub fn state_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("lazynews").join(STATE_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::posts_view::PostSort;
+    use std::{env, process};
+
+    #[test]
+    fn round_trips_through_a_file() {
+        let path = env::temp_dir()
+            .join(format!("lazynews-state-test-{}", process::id()))
+            .join(STATE_FILE_NAME);
+        assert_eq!(SavedState::load(&path).unwrap(), SavedState::default());
+
+        let mut state = SavedState::default();
+        assert!(state.toggle_follow("alice"));
+        assert!(state.toggle_follow("bob"));
+        assert!(!state.toggle_follow("alice"));
+        state.mutes.mute_author("spammer");
+        state.posts_views.insert(
+            "top".to_string(),
+            PostsView {
+                sort: PostSort::Points,
+                min_points: Some(100),
+                ..PostsView::default()
+            },
+        );
+        state.save(&path).unwrap();
+
+        let loaded = SavedState::load(&path).unwrap();
+        assert_eq!(loaded.followed_users, vec!["bob".to_string()]);
+        assert!(loaded.is_followed("bob"));
+        assert_eq!(loaded, state);
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -1,7 +1,12 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::hn::Comment;
+use crate::{
+    hn::{Comment, PollOption},
+    html::{Block, Inline},
+    reader::{Article, ArticleBlock},
+};
 use ratatui::{
+    layout::Rect,
     style::{Color, Style, Stylize},
     text::{Line, Span},
 };
@@ -10,10 +15,18 @@ pub const POST_SELECTED_COLOR: Color = Color::Rgb(255, 149, 0);
 pub const PANE_SHORTCUT_COLOR: Color = POST_SELECTED_COLOR;
 pub const POST_META_COLOR: Color = Color::Rgb(140, 140, 140);
 pub const COMMENT_AUTHOR_COLOR: Color = Color::Rgb(255, 149, 0);
+pub const COMMENT_FOLLOWED_COLOR: Color = Color::Rgb(110, 210, 160);
+pub const COMMENT_OP_COLOR: Color = Color::Rgb(255, 215, 90);
+pub const ALERT_BADGE_COLOR: Color = Color::Rgb(255, 95, 95);
 pub const COMMENT_TEXT_COLOR: Color = Color::Rgb(225, 225, 225);
 pub const COMMENT_QUOTE_COLOR: Color = POST_META_COLOR;
+pub const COMMENT_LINK_COLOR: Color = Color::Rgb(120, 170, 255);
+pub const COMMENT_CODE_COLOR: Color = Color::Rgb(170, 200, 140);
 pub const COMMENT_INDENT_COLOR: Color = Color::Rgb(90, 90, 90);
 pub const COMMENT_BORDER_COLOR: Color = Color::Rgb(255, 149, 0);
+pub const POLL_BAR_COLOR: Color = Color::Rgb(255, 149, 0);
+pub const COMMENT_FOCUS_COLOR: Color = Color::Rgb(255, 149, 0);
+pub const COMMENT_GUTTER_WIDTH: usize = 1;
 pub const SPINNER_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,6 +35,8 @@ pub enum InstructionsPane {
     Bookmarks,
     Posts,
     Comments,
+    Reader,
+    Alerts,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,6 +45,8 @@ pub enum Pane {
     Bookmarks,
     Posts,
     Comments,
+    Reader,
+    Alerts,
 }
 
 pub fn instructions_pane_for(pane: Pane) -> InstructionsPane {
@@ -38,6 +55,8 @@ pub fn instructions_pane_for(pane: Pane) -> InstructionsPane {
         Pane::Bookmarks => InstructionsPane::Bookmarks,
         Pane::Posts => InstructionsPane::Posts,
         Pane::Comments => InstructionsPane::Comments,
+        Pane::Reader => InstructionsPane::Reader,
+        Pane::Alerts => InstructionsPane::Alerts,
     }
 }
 
@@ -69,6 +88,17 @@ pub fn pane_title_with_shortcut(
     ])
 }
 
+/// Bottom-border notice for items that still failed after retries.
+pub fn failed_items_line(count: usize) -> Option<Line<'static>> {
+    let noun = if count == 1 { "item" } else { "items" };
+    (count > 0).then(|| {
+        Line::styled(
+            format!(" {count} {noun} failed to load, press Shift-R to retry "),
+            Style::new().fg(ALERT_BADGE_COLOR),
+        )
+    })
+}
+
 pub fn instructions_line(
     active_pane: InstructionsPane,
     comments_open: bool,
@@ -81,7 +111,13 @@ pub fn instructions_line(
     let key =
         |label: &'static str| Span::styled(label, Style::new().fg(POST_SELECTED_COLOR).bold());
 
-    spans.extend(["Pane ".into(), key("<Tab/Shift-Tab>"), " ".into()]);
+    spans.extend([
+        "Pane ".into(),
+        key("<Tab/Shift-Tab>"),
+        " Commands ".into(),
+        key("<:>"),
+        " ".into(),
+    ]);
 
     match active_pane {
         InstructionsPane::Feeds => spans.extend([
@@ -109,6 +145,22 @@ pub fn instructions_line(
                 key("<Enter>"),
                 " Open ".into(),
                 key("<O>"),
+                " Discussion ".into(),
+                key("<D>"),
+                " Reader ".into(),
+                key("<V>"),
+                " Author ".into(),
+                key("<U>"),
+                " Copy ".into(),
+                key("<Y>"),
+                " Mute ".into(),
+                key("<M>"),
+                " Domain ".into(),
+                key("<F>"),
+                " Sort ".into(),
+                key("<S>"),
+                " Filter ".into(),
+                key("<P/C/A/X/Z>"),
             ]);
             if comments_open {
                 spans.extend([" Close comments ".into(), key("<Esc>")]);
@@ -149,12 +201,60 @@ pub fn instructions_line(
                 key("<B>"),
                 " Open ".into(),
                 key("<O>"),
+                " Discussion ".into(),
+                key("<D>"),
+                " Links ".into(),
+                key("<F>"),
+                " Copy ".into(),
+                key("<Y>"),
+                " Reader ".into(),
+                key("<V>"),
+                " Author ".into(),
+                key("<U>"),
+                " Next OP ".into(),
+                key("<N>"),
+                " Thread ".into(),
+                key("<P/T>"),
+                " Mute ".into(),
+                key("<M/X>"),
+                " Order ".into(),
+                key("<S>"),
                 " Close ".into(),
                 key("<Esc>"),
                 " Quit ".into(),
                 key("<Q> "),
             ]);
         }
+        InstructionsPane::Reader => {
+            spans.extend([
+                "Scroll ".into(),
+                key("<Up/Down/J/K>"),
+                " Page ".into(),
+                key("<PgUp/PgDn>"),
+                " Open ".into(),
+                key("<O>"),
+                " Close ".into(),
+                key("<Esc>"),
+                " Quit ".into(),
+                key("<Q> "),
+            ]);
+        }
+        InstructionsPane::Alerts => {
+            spans.extend([
+                "Move ".into(),
+                key("<Up/Down/J/K>"),
+                " Comments ".into(),
+                key("<Enter>"),
+                " Open ".into(),
+                key("<O>"),
+                " Dismiss ".into(),
+                key("<D>"),
+                " Clear ".into(),
+                key("<Shift-D>"),
+                " Quit ".into(),
+                key("<Q> "),
+            ]);
+        }
     }
 
     if matches!(active_pane, InstructionsPane::Bookmarks) && !bookmarks_visible {
@@ -164,99 +264,282 @@ pub fn instructions_line(
     Line::from(spans)
 }
 
+pub fn popup_area(area: Rect, width_percent: u16, height: u16) -> Rect {
+    let width = (area.width as u32 * width_percent.min(100) as u32 / 100) as u16;
+    let width = width.max(20).min(area.width);
+    let height = height.min(area.height);
+    let x = area.x + (area.width - width) / 2;
+    let y = area.y + (area.height - height) / 3;
+
+    Rect::new(x, y, width, height)
+}
+
+/// What the comments pane is showing, along with the authors to highlight.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CommentsView<'a> {
+    pub post_id: Option<u64>,
+    pub loading: bool,
+    pub notice: Option<&'a str>,
+    pub error: Option<&'a str>,
+    pub comments: &'a [Comment],
+    pub followed_users: &'a [String],
+    /// Muted comments the user chose to show anyway.
+    pub revealed: &'a [u64],
+    /// Index of the comment marked in the gutter as the target of actions.
+    pub focused: Option<usize>,
+    /// Submitter of the post, whose comments get an OP badge.
+    pub post_author: Option<&'a str>,
+    /// Body of a self post, shown above the comments.
+    pub post_body: &'a [Block],
+    /// Options of a poll, drawn as a bar chart below the post body.
+    pub poll_options: &'a [PollOption],
+}
+
 pub fn comment_lines(
     spinner: &str,
     content_width: usize,
-    comments_for_post_id: Option<u64>,
-    comments_loading: bool,
-    comments_notice: Option<&str>,
-    comments_error: Option<&str>,
-    comments: &[Comment],
+    view: &CommentsView,
 ) -> (Vec<Line<'static>>, Vec<u16>) {
-    if comments_for_post_id.is_none() {
+    let comments = view.comments;
+    if view.post_id.is_none() {
         return (
             vec![Line::from("Press Enter on a post to load comments.")],
             Vec::new(),
         );
     }
 
-    if comments_loading && comments.is_empty() {
-        return (
-            vec![Line::from(format!("Loading comments {spinner}"))],
-            Vec::new(),
-        );
+    let mut lines = post_header_lines(view.post_body, view.poll_options, content_width);
+    let status = if view.loading && comments.is_empty() {
+        Some(Line::from(format!("Loading comments {spinner}")))
+    } else if let Some(message) = view.notice {
+        Some(Line::from(message.to_string()).style(Style::new().fg(POST_META_COLOR)))
+    } else if let Some(err) = view.error {
+        Some(Line::from(format!("Failed to load comments: {err}")))
+    } else if comments.is_empty() {
+        Some(Line::from("No comments found."))
+    } else {
+        None
+    };
+    if let Some(status) = status {
+        lines.push(status);
+        return (lines, Vec::new());
     }
 
-    if let Some(message) = comments_notice {
-        return (
-            vec![Line::from(message.to_string()).style(Style::new().fg(POST_META_COLOR))],
-            Vec::new(),
-        );
-    }
+    let mut comment_start_lines: Vec<u16> = Vec::with_capacity(comments.len());
+    let content_width = content_width.saturating_sub(COMMENT_GUTTER_WIDTH);
 
-    if let Some(err) = comments_error {
-        return (
-            vec![Line::from(format!("Failed to load comments: {err}"))],
-            Vec::new(),
-        );
-    }
+    for (index, comment) in comments.iter().enumerate() {
+        let start = lines.len();
+        comment_start_lines.push(start as u16);
+        push_comment_lines(&mut lines, comment, content_width, view);
 
-    if comments.is_empty() {
-        return (vec![Line::from("No comments found.")], Vec::new());
+        let gutter = if view.focused == Some(index) {
+            Span::styled("▌", Style::new().fg(COMMENT_FOCUS_COLOR))
+        } else {
+            Span::raw(" ")
+        };
+        for line in &mut lines[start..] {
+            line.spans.insert(0, gutter.clone());
+        }
     }
 
-    let mut lines: Vec<Line<'static>> = Vec::new();
-    let mut comment_start_lines: Vec<u16> = Vec::with_capacity(comments.len());
+    (lines, comment_start_lines)
+}
 
-    for comment in comments {
-        comment_start_lines.push(lines.len() as u16);
-        let (header_prefix, body_prefix) = tree_prefix(comment);
+fn push_comment_lines(
+    lines: &mut Vec<Line<'static>>,
+    comment: &Comment,
+    content_width: usize,
+    view: &CommentsView,
+) {
+    let (header_prefix, body_prefix) = tree_prefix(comment);
 
-        let mut header_spans: Vec<Span> = Vec::new();
-        if !header_prefix.is_empty() {
-            header_spans.push(Span::styled(
-                header_prefix,
-                Style::new().fg(COMMENT_INDENT_COLOR),
-            ));
-        }
+    let mut header_spans: Vec<Span> = Vec::new();
+    if !header_prefix.is_empty() {
         header_spans.push(Span::styled(
-            comment.author.clone(),
-            Style::new().fg(COMMENT_AUTHOR_COLOR).bold(),
+            header_prefix,
+            Style::new().fg(COMMENT_INDENT_COLOR),
         ));
-        header_spans.push(Span::styled(" • ", Style::new().fg(POST_META_COLOR)));
+    }
+    if comment.muted && !view.revealed.contains(&comment.id) {
         header_spans.push(Span::styled(
-            format_age(comment.published_at),
-            Style::new().fg(POST_META_COLOR),
+            "[muted]",
+            Style::new().fg(POST_META_COLOR).italic(),
         ));
         lines.push(Line::from(header_spans));
+        return;
+    }
+    let is_op = view.post_author == Some(comment.author.as_str());
+    let followed = view.followed_users.contains(&comment.author);
+    let author_color = if is_op {
+        COMMENT_OP_COLOR
+    } else if followed {
+        COMMENT_FOLLOWED_COLOR
+    } else {
+        COMMENT_AUTHOR_COLOR
+    };
+    header_spans.push(Span::styled(
+        comment.author.clone(),
+        Style::new().fg(author_color).bold(),
+    ));
+    if is_op {
+        header_spans.push(Span::raw(" "));
+        header_spans.push(Span::styled(
+            " OP ",
+            Style::new().fg(Color::Black).bg(COMMENT_OP_COLOR).bold(),
+        ));
+    }
+    if followed {
+        header_spans.push(Span::styled(" ★", Style::new().fg(COMMENT_FOLLOWED_COLOR)));
+    }
+    header_spans.push(Span::styled(" • ", Style::new().fg(POST_META_COLOR)));
+    header_spans.push(Span::styled(
+        format_age(comment.published_at),
+        Style::new().fg(POST_META_COLOR),
+    ));
+    lines.push(Line::from(header_spans));
+
+    let prefix_width = body_prefix.chars().count();
+    let text_width = content_width.saturating_sub(prefix_width).max(1);
+    for block in &comment.body {
+        for segments in block_lines(block, text_width) {
+            let mut body_spans: Vec<Span> = Vec::new();
+            if !body_prefix.is_empty() {
+                body_spans.push(Span::styled(
+                    body_prefix.clone(),
+                    Style::new().fg(COMMENT_INDENT_COLOR),
+                ));
+            }
+            body_spans.extend(segments);
+            lines.push(Line::from(body_spans));
+        }
+    }
+}
 
-        for comment_line in comment.text.lines() {
-            let is_quote = comment_line.trim_start().starts_with('>');
-            let text_style = if is_quote {
-                Style::new().fg(COMMENT_QUOTE_COLOR)
-            } else {
-                Style::new().fg(COMMENT_TEXT_COLOR)
+/// Renders a comment block into styled lines. Paragraphs are wrapped to `width`;
+/// preformatted blocks keep their whitespace and are left unwrapped.
+fn block_lines(block: &Block, width: usize) -> Vec<Vec<Span<'static>>> {
+    let inlines = match block {
+        Block::Preformatted(text) => {
+            return text
+                .lines()
+                .map(|line| {
+                    vec![Span::styled(
+                        line.to_string(),
+                        Style::new().fg(COMMENT_CODE_COLOR),
+                    )]
+                })
+                .collect();
+        }
+        Block::Paragraph(inlines) => inlines,
+    };
+
+    let mut lines = Vec::new();
+    for line_inlines in inlines.split(|inline| matches!(inline, Inline::Break)) {
+        let plain: String = line_inlines.iter().map(Inline::display_text).collect();
+        let base_style = if plain.trim_start().starts_with('>') {
+            Style::new().fg(COMMENT_QUOTE_COLOR)
+        } else {
+            Style::new().fg(COMMENT_TEXT_COLOR)
+        };
+
+        // One style per non-whitespace character, in order, so wrapped lines can be
+        // re-styled after `wrap_text` has rearranged the whitespace.
+        let mut char_styles: Vec<Style> = Vec::with_capacity(plain.len());
+        for inline in line_inlines {
+            let style = match inline {
+                Inline::Text(_) | Inline::Break => base_style,
+                Inline::Italic(_) => base_style.italic(),
+                Inline::Link { .. } => Style::new().fg(COMMENT_LINK_COLOR).underlined(),
             };
+            let visible = inline
+                .display_text()
+                .chars()
+                .filter(|ch| !ch.is_whitespace())
+                .count();
+            char_styles.extend(std::iter::repeat_n(style, visible));
+        }
 
-            let prefix_width = body_prefix.chars().count();
-            let text_width = content_width.saturating_sub(prefix_width).max(1);
-            let wrapped_segments = wrap_text(comment_line, text_width);
-
-            for segment in wrapped_segments {
-                let mut body_spans: Vec<Span> = Vec::new();
-                if !body_prefix.is_empty() {
-                    body_spans.push(Span::styled(
-                        body_prefix.clone(),
-                        Style::new().fg(COMMENT_INDENT_COLOR),
-                    ));
+        let mut styles = char_styles.into_iter();
+        for segment in wrap_text(&plain, width) {
+            let mut spans: Vec<Span<'static>> = Vec::new();
+            let mut current = String::new();
+            let mut current_style = base_style;
+            for ch in segment.chars() {
+                let style = if ch.is_whitespace() {
+                    base_style
+                } else {
+                    styles.next().unwrap_or(base_style)
+                };
+                if style != current_style && !current.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut current), current_style));
                 }
-                body_spans.push(Span::styled(segment, text_style));
-                lines.push(Line::from(body_spans));
+                current_style = style;
+                current.push(ch);
             }
+            if !current.is_empty() || spans.is_empty() {
+                spans.push(Span::styled(current, current_style));
+            }
+            lines.push(spans);
         }
     }
 
-    (lines, comment_start_lines)
+    lines
+}
+
+/// Renders an extracted article as wrapped lines: headings in bold, quotes and
+/// list items indented, preformatted text left unwrapped.
+pub fn article_lines(article: &Article, width: usize) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let text_style = Style::new().fg(COMMENT_TEXT_COLOR);
+
+    for block in &article.blocks {
+        if !lines.is_empty() {
+            lines.push(Line::default());
+        }
+
+        match block {
+            ArticleBlock::Heading(text) => {
+                lines.extend(wrap_paragraph(text, width).into_iter().map(|line| {
+                    Line::from(line).style(Style::new().fg(POST_SELECTED_COLOR).bold())
+                }))
+            }
+            ArticleBlock::Paragraph(text) => lines.extend(
+                wrap_paragraph(text, width)
+                    .into_iter()
+                    .map(|line| Line::from(line).style(text_style)),
+            ),
+            ArticleBlock::Quote(text) => lines.extend(
+                wrap_paragraph(text, width.saturating_sub(2))
+                    .into_iter()
+                    .map(|line| {
+                        Line::from(format!("> {line}")).style(Style::new().fg(COMMENT_QUOTE_COLOR))
+                    }),
+            ),
+            ArticleBlock::ListItem(text) => {
+                lines.extend(
+                    wrap_paragraph(text, width.saturating_sub(2))
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, line)| {
+                            let bullet = if index == 0 { "• " } else { "  " };
+                            Line::from(format!("{bullet}{line}")).style(text_style)
+                        }),
+                );
+            }
+            ArticleBlock::Preformatted(text) => lines.extend(text.lines().map(|line| {
+                Line::from(line.to_string()).style(Style::new().fg(COMMENT_CODE_COLOR))
+            })),
+        }
+    }
+
+    lines
+}
+
+fn wrap_paragraph(text: &str, width: usize) -> Vec<String> {
+    text.lines()
+        .flat_map(|line| wrap_text(line, width))
+        .collect()
 }
 
 pub fn format_age(unix_seconds: u64) -> String {
@@ -281,6 +564,64 @@ pub fn format_age(unix_seconds: u64) -> String {
     }
 }
 
+/// The self-post body and poll results, followed by a rule separating them
+/// from the comments.
+fn post_header_lines(
+    body: &[Block],
+    poll_options: &[PollOption],
+    content_width: usize,
+) -> Vec<Line<'static>> {
+    if body.is_empty() && poll_options.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines: Vec<Line<'static>> = body
+        .iter()
+        .flat_map(|block| block_lines(block, content_width.max(1)))
+        .map(Line::from)
+        .collect();
+    if !body.is_empty() && !poll_options.is_empty() {
+        lines.push(Line::default());
+    }
+    lines.extend(poll_lines(poll_options, content_width));
+    lines.push(Line::styled(
+        "─".repeat(content_width),
+        Style::new().fg(COMMENT_INDENT_COLOR),
+    ));
+    lines
+}
+
+/// Each option's text above a bar scaled to the most voted option, labelled
+/// with its score and share of all votes.
+fn poll_lines(options: &[PollOption], content_width: usize) -> Vec<Line<'static>> {
+    let total: u64 = options.iter().map(|option| option.score).sum();
+    let max = options.iter().map(|option| option.score).max().unwrap_or(0);
+    let mut lines = Vec::with_capacity(options.len() * 2);
+
+    for option in options {
+        lines.extend(
+            wrap_text(&option.text, content_width.max(1))
+                .into_iter()
+                .map(|line| Line::from(line).style(Style::new().fg(COMMENT_TEXT_COLOR))),
+        );
+
+        let percent = (option.score * 100).checked_div(total).unwrap_or(0);
+        let label = format!(" {} ({percent}%)", option.score);
+        let bar_width = content_width.saturating_sub(label.chars().count());
+        let filled = if max == 0 {
+            0
+        } else {
+            (option.score as usize * bar_width).div_ceil(max as usize)
+        };
+        lines.push(Line::from(vec![
+            Span::styled("█".repeat(filled), Style::new().fg(POLL_BAR_COLOR)),
+            Span::styled(label, Style::new().fg(POST_META_COLOR)),
+        ]));
+    }
+
+    lines
+}
+
 fn tree_prefix(comment: &Comment) -> (String, String) {
     let mut header_prefix = String::new();
     let mut body_prefix = if comment.depth == 0 {
@@ -393,6 +734,8 @@ fn wrap_text(input: &str, width: usize) -> Vec<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::html::parse_hn_html;
+    use ratatui::style::Modifier;
 
     fn as_text(line: &Line<'_>) -> String {
         line.spans
@@ -409,12 +752,14 @@ mod tests {
         is_last_sibling: bool,
     ) -> Comment {
         Comment {
+            id: 0,
             author: author.to_string(),
-            text: text.to_string(),
+            body: parse_hn_html(text),
             published_at: 1,
             depth,
             ancestor_has_next_sibling,
             is_last_sibling,
+            muted: false,
         }
     }
 
@@ -444,7 +789,7 @@ mod tests {
 
     #[test]
     fn comment_lines_returns_placeholder_states() {
-        let (lines, starts) = comment_lines("|", 40, None, false, None, None, &[]);
+        let (lines, starts) = comment_lines("|", 40, &CommentsView::default());
         assert_eq!(lines.len(), 1);
         assert_eq!(
             as_text(&lines[0]),
@@ -452,23 +797,53 @@ mod tests {
         );
         assert!(starts.is_empty());
 
-        let (lines, starts) = comment_lines("|", 40, Some(1), true, None, None, &[]);
+        let (lines, starts) = comment_lines(
+            "|",
+            40,
+            &CommentsView {
+                post_id: Some(1),
+                loading: true,
+                ..CommentsView::default()
+            },
+        );
         assert_eq!(lines.len(), 1);
         assert_eq!(as_text(&lines[0]), "Loading comments |");
         assert!(starts.is_empty());
 
-        let (lines, starts) =
-            comment_lines("|", 40, Some(1), false, Some("no comments"), None, &[]);
+        let (lines, starts) = comment_lines(
+            "|",
+            40,
+            &CommentsView {
+                post_id: Some(1),
+                notice: Some("no comments"),
+                ..CommentsView::default()
+            },
+        );
         assert_eq!(lines.len(), 1);
         assert_eq!(as_text(&lines[0]), "no comments");
         assert!(starts.is_empty());
 
-        let (lines, starts) = comment_lines("|", 40, Some(1), false, None, Some("boom"), &[]);
+        let (lines, starts) = comment_lines(
+            "|",
+            40,
+            &CommentsView {
+                post_id: Some(1),
+                error: Some("boom"),
+                ..CommentsView::default()
+            },
+        );
         assert_eq!(lines.len(), 1);
         assert_eq!(as_text(&lines[0]), "Failed to load comments: boom");
         assert!(starts.is_empty());
 
-        let (lines, starts) = comment_lines("|", 40, Some(1), false, None, None, &[]);
+        let (lines, starts) = comment_lines(
+            "|",
+            40,
+            &CommentsView {
+                post_id: Some(1),
+                ..CommentsView::default()
+            },
+        );
         assert_eq!(lines.len(), 1);
         assert_eq!(as_text(&lines[0]), "No comments found.");
         assert!(starts.is_empty());
@@ -477,7 +852,16 @@ mod tests {
     #[test]
     fn comment_lines_renders_cached_comments_while_refreshing() {
         let comments = vec![sample_comment("alice", "cached text", 0, vec![], true)];
-        let (lines, starts) = comment_lines("|", 40, Some(1), true, None, None, &comments);
+        let (lines, starts) = comment_lines(
+            "|",
+            40,
+            &CommentsView {
+                post_id: Some(1),
+                loading: true,
+                comments: &comments,
+                ..CommentsView::default()
+            },
+        );
         let rendered: Vec<String> = lines.iter().map(as_text).collect();
 
         assert_eq!(starts, vec![0]);
@@ -497,7 +881,15 @@ mod tests {
             sample_comment("bob", "> quoted\nreply", 1, vec![true], true),
         ];
 
-        let (lines, starts) = comment_lines("|", 24, Some(42), false, None, None, &comments);
+        let (lines, starts) = comment_lines(
+            "|",
+            24,
+            &CommentsView {
+                post_id: Some(42),
+                comments: &comments,
+                ..CommentsView::default()
+            },
+        );
         let rendered: Vec<String> = lines.iter().map(as_text).collect();
 
         assert_eq!(starts, vec![0, 2]);
@@ -509,6 +901,234 @@ mod tests {
         assert!(rendered[4].contains("reply"));
     }
 
+    #[test]
+    fn comment_lines_collapse_muted_comments_until_revealed() {
+        let mut muted = sample_comment("troll", "noise", 0, vec![], false);
+        muted.id = 7;
+        muted.muted = true;
+        let comments = vec![muted, sample_comment("bob", "signal", 0, vec![], true)];
+
+        let (lines, starts) = comment_lines(
+            "|",
+            40,
+            &CommentsView {
+                post_id: Some(1),
+                comments: &comments,
+                ..CommentsView::default()
+            },
+        );
+        let rendered: Vec<String> = lines.iter().map(as_text).collect();
+        assert_eq!(starts, vec![0, 1]);
+        assert_eq!(rendered[0], " [muted]");
+        assert!(!rendered.iter().any(|line| line.contains("noise")));
+
+        let (lines, _) = comment_lines(
+            "|",
+            40,
+            &CommentsView {
+                post_id: Some(1),
+                comments: &comments,
+                revealed: &[7],
+                ..CommentsView::default()
+            },
+        );
+        assert!(lines.iter().map(as_text).any(|line| line.contains("noise")));
+    }
+
+    #[test]
+    fn comment_lines_put_the_post_body_before_comments() {
+        let body = parse_hn_html("What do you use?<p>Asking for a friend.");
+        let comments = vec![sample_comment("alice", "vim", 0, vec![], true)];
+
+        let (lines, starts) = comment_lines(
+            "|",
+            10,
+            &CommentsView {
+                post_id: Some(1),
+                comments: &comments,
+                post_body: &body,
+                ..CommentsView::default()
+            },
+        );
+        let rendered: Vec<String> = lines.iter().map(as_text).collect();
+        let separator = rendered
+            .iter()
+            .position(|line| line == &"─".repeat(10))
+            .expect("separator line");
+        assert!(rendered[..separator].join(" ").contains("Asking for a"));
+        assert_eq!(starts, vec![separator as u16 + 1]);
+
+        let (lines, starts) = comment_lines(
+            "|",
+            10,
+            &CommentsView {
+                post_id: Some(1),
+                post_body: &body,
+                ..CommentsView::default()
+            },
+        );
+        assert!(starts.is_empty());
+        assert_eq!(as_text(lines.last().unwrap()), "No comments found.");
+        assert!(lines.len() > 2);
+    }
+
+    #[test]
+    fn comment_lines_chart_poll_options() {
+        let options = vec![
+            PollOption {
+                text: "Tabs".to_string(),
+                score: 30,
+            },
+            PollOption {
+                text: "Spaces".to_string(),
+                score: 10,
+            },
+        ];
+
+        let (lines, starts) = comment_lines(
+            "|",
+            20,
+            &CommentsView {
+                post_id: Some(1),
+                poll_options: &options,
+                ..CommentsView::default()
+            },
+        );
+        let rendered: Vec<String> = lines.iter().map(as_text).collect();
+        assert!(starts.is_empty());
+        assert_eq!(rendered[0], "Tabs");
+        assert_eq!(rendered[1], format!("{} 30 (75%)", "█".repeat(11)));
+        assert_eq!(rendered[2], "Spaces");
+        assert_eq!(rendered[3], format!("{} 10 (25%)", "█".repeat(4)));
+        assert_eq!(rendered[4], "─".repeat(20));
+    }
+
+    #[test]
+    fn comment_lines_highlight_followed_authors() {
+        let comments = vec![
+            sample_comment("alice", "hi", 0, vec![], false),
+            sample_comment("bob", "hey", 0, vec![], true),
+        ];
+        let followed = vec!["bob".to_string()];
+
+        let (lines, starts) = comment_lines(
+            "|",
+            40,
+            &CommentsView {
+                post_id: Some(1),
+                comments: &comments,
+                followed_users: &followed,
+                ..CommentsView::default()
+            },
+        );
+
+        let alice = &lines[starts[0] as usize];
+        let bob = &lines[starts[1] as usize];
+        assert_eq!(alice.spans[1].style.fg, Some(COMMENT_AUTHOR_COLOR));
+        assert_eq!(bob.spans[1].style.fg, Some(COMMENT_FOLLOWED_COLOR));
+        assert!(as_text(bob).starts_with(" bob ★"));
+    }
+
+    #[test]
+    fn comment_lines_mark_the_focused_comment_in_the_gutter() {
+        let comments = vec![
+            sample_comment("alice", "hi", 0, vec![], false),
+            sample_comment("bob", "hey", 0, vec![], true),
+        ];
+
+        let (lines, starts) = comment_lines(
+            "|",
+            40,
+            &CommentsView {
+                post_id: Some(1),
+                comments: &comments,
+                focused: Some(1),
+                ..CommentsView::default()
+            },
+        );
+
+        let gutters: Vec<&str> = lines
+            .iter()
+            .map(|line| line.spans[0].content.as_ref())
+            .collect();
+        let bob = starts[1] as usize;
+        assert!(gutters[..bob].iter().all(|gutter| *gutter == " "));
+        assert!(gutters[bob..].iter().all(|gutter| *gutter == "▌"));
+        assert_eq!(lines[bob].spans[0].style.fg, Some(COMMENT_FOCUS_COLOR));
+    }
+
+    #[test]
+    fn comment_lines_badge_the_original_poster() {
+        let comments = vec![
+            sample_comment("alice", "question?", 0, vec![], false),
+            sample_comment("bob", "answer", 0, vec![], true),
+        ];
+
+        let (lines, starts) = comment_lines(
+            "|",
+            40,
+            &CommentsView {
+                post_id: Some(1),
+                comments: &comments,
+                post_author: Some("alice"),
+                ..CommentsView::default()
+            },
+        );
+
+        let alice = &lines[starts[0] as usize];
+        let bob = &lines[starts[1] as usize];
+        assert_eq!(alice.spans[1].style.fg, Some(COMMENT_OP_COLOR));
+        assert!(as_text(alice).starts_with(" alice  OP  •"));
+        assert_eq!(bob.spans[1].style.fg, Some(COMMENT_AUTHOR_COLOR));
+        assert!(!as_text(bob).contains("OP"));
+    }
+
+    #[test]
+    fn article_lines_wrap_paragraphs_and_separate_blocks() {
+        let article = Article {
+            title: None,
+            blocks: vec![
+                ArticleBlock::Heading("Title".to_string()),
+                ArticleBlock::Paragraph("alpha beta gamma".to_string()),
+                ArticleBlock::ListItem("one two three".to_string()),
+                ArticleBlock::Preformatted("let x = 1;   // long line kept".to_string()),
+            ],
+        };
+
+        let rendered: Vec<String> = article_lines(&article, 10)
+            .iter()
+            .map(|line| as_text(line))
+            .collect();
+
+        assert_eq!(
+            rendered,
+            vec![
+                "Title",
+                "",
+                "alpha beta",
+                "gamma",
+                "",
+                "• one two",
+                "  three",
+                "",
+                "let x = 1;   // long line kept",
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_items_line_counts_items() {
+        assert!(failed_items_line(0).is_none());
+        assert_eq!(
+            as_text(&failed_items_line(1).unwrap()),
+            " 1 item failed to load, press Shift-R to retry "
+        );
+        assert_eq!(
+            as_text(&failed_items_line(3).unwrap()),
+            " 3 items failed to load, press Shift-R to retry "
+        );
+    }
+
     #[test]
     fn format_age_returns_dash_for_zero_timestamp() {
         assert_eq!(format_age(0), "-");
@@ -521,6 +1141,7 @@ mod tests {
             InstructionsPane::Bookmarks,
             InstructionsPane::Posts,
             InstructionsPane::Comments,
+            InstructionsPane::Reader,
         ];
 
         for pane in panes {
@@ -529,6 +1150,7 @@ mod tests {
 
             assert!(text.contains("Pane"));
             assert!(text.contains("<Tab/Shift-Tab>"));
+            assert!(text.contains("<:>"));
             assert!(!text.contains("<1>"));
             assert!(!text.contains("<2>"));
             assert!(!text.contains("<3>"));
@@ -568,4 +1190,55 @@ mod tests {
         assert!(text.contains("<A>"));
         assert!(text.contains("Open all"));
     }
+
+    #[test]
+    fn popup_area_is_centered_and_clamped() {
+        let area = Rect::new(0, 0, 100, 30);
+        let popup = popup_area(area, 60, 10);
+        assert_eq!(popup, Rect::new(20, 6, 60, 10));
+
+        let popup = popup_area(Rect::new(0, 0, 10, 5), 60, 10);
+        assert_eq!(popup, Rect::new(0, 0, 10, 5));
+    }
+
+    #[test]
+    fn comment_lines_style_rich_text_and_keep_code_unwrapped() {
+        let comments = vec![sample_comment(
+            "alice",
+            "See <i>this</i> <a href=\"https://example.com\">https://example.com</a><pre><code>  let x = 1; // a long line</code></pre>",
+            0,
+            vec![],
+            true,
+        )];
+
+        let (lines, _) = comment_lines(
+            "|",
+            40,
+            &CommentsView {
+                post_id: Some(1),
+                comments: &comments,
+                ..CommentsView::default()
+            },
+        );
+        let rendered: Vec<String> = lines.iter().map(as_text).collect();
+
+        let italic = lines[1]
+            .spans
+            .iter()
+            .find(|span| span.content == "this")
+            .expect("italic span");
+        assert!(italic.style.add_modifier.contains(Modifier::ITALIC));
+        assert!(
+            rendered
+                .iter()
+                .any(|line| line.contains("https://example.com"))
+        );
+        let link = lines
+            .iter()
+            .flat_map(|line| line.spans.iter())
+            .find(|span| span.content.contains("example.com"))
+            .expect("link span");
+        assert_eq!(link.style.fg, Some(COMMENT_LINK_COLOR));
+        assert_eq!(rendered.last().unwrap(), "      let x = 1; // a long line");
+    }
 }
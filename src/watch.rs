@@ -0,0 +1,212 @@
+use crate::{
+    hn::{Item, url_domain},
+    html::decode_html_entities,
+    mute::{compile_keyword, host_matches_domain},
+};
+use regex::Regex;
+use serde::Deserialize;
+
+/// A `[[watch]]` entry from the config file. Every condition that is set must
+/// match; a rule without any conditions never matches.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct WatchRule {
+    /// Case-insensitive title substring, or a regex when wrapped in slashes.
+    pub keyword: Option<String>,
+    /// Matches the domain and its subdomains.
+    pub domain: Option<String>,
+    pub min_points: Option<u64>,
+}
+
+impl WatchRule {
+    /// Short description shown in the alerts pane, e.g. `rust ≥100`.
+    pub fn label(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(keyword) = self.keyword.as_deref() {
+            parts.push(keyword.to_string());
+        }
+        if let Some(domain) = self.domain.as_deref() {
+            parts.push(domain.to_string());
+        }
+        if let Some(min_points) = self.min_points {
+            parts.push(format!("≥{min_points}"));
+        }
+        parts.join(" ")
+    }
+
+    fn is_empty(&self) -> bool {
+        self.keyword.is_none() && self.domain.is_none() && self.min_points.is_none()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    label: String,
+    keyword: Option<Regex>,
+    domain: Option<String>,
+    min_points: Option<u64>,
+}
+
+impl CompiledRule {
+    fn matches(&self, title: &str, host: Option<&str>, points: u64) -> bool {
+        self.keyword
+            .as_ref()
+            .is_none_or(|keyword| keyword.is_match(title))
+            && self
+                .domain
+                .as_deref()
+                .is_none_or(|domain| host.is_some_and(|host| host_matches_domain(host, domain)))
+            && self
+                .min_points
+                .is_none_or(|min_points| points >= min_points)
+    }
+}
+
+/// Compiled watch rules, checked against every fetched story.
+#[derive(Debug, Clone, Default)]
+pub struct WatchList {
+    rules: Vec<CompiledRule>,
+}
+
+impl WatchList {
+    /// Rules with an invalid regex are skipped and reported in the error message.
+    pub fn new(rules: &[WatchRule]) -> (Self, Option<String>) {
+        let mut errors = Vec::new();
+        let rules = rules
+            .iter()
+            .filter(|rule| !rule.is_empty())
+            .filter_map(|rule| {
+                let keyword = match rule.keyword.as_deref().map(compile_keyword) {
+                    Some(Ok(regex)) => Some(regex),
+                    Some(Err(error)) => {
+                        errors.push(format!("Invalid watch rule {}: {error}", rule.label()));
+                        return None;
+                    }
+                    None => None,
+                };
+                Some(CompiledRule {
+                    label: rule.label(),
+                    keyword,
+                    domain: rule
+                        .domain
+                        .as_deref()
+                        .map(|domain| domain.trim_start_matches("www.").to_ascii_lowercase()),
+                    min_points: rule.min_points,
+                })
+            })
+            .collect();
+
+        (Self { rules }, errors.into_iter().next())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The label of the first rule matching a live story or job.
+    pub fn matching_rule(&self, item: &Item) -> Option<&str> {
+        if item.dead || item.deleted {
+            return None;
+        }
+        let title = decode_html_entities(item.title.as_deref()?);
+        let host = item.url.as_deref().and_then(url_domain);
+        let points = item.score.unwrap_or_default();
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(&title, host.as_deref(), points))
+            .map(|rule| rule.label.as_str())
+    }
+}
+
+/// A story that matched a watch rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alert {
+    pub item_id: u64,
+    pub title: String,
+    pub url: String,
+    pub points: u64,
+    pub rule: String,
+}
+
+impl Alert {
+    pub fn new(item: &Item, rule: &str) -> Self {
+        Self {
+            item_id: item.id,
+            title: decode_html_entities(item.title.as_deref().unwrap_or_default()),
+            url: item.url.clone().unwrap_or_default(),
+            points: item.score.unwrap_or_default(),
+            rule: rule.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(title: &str, url: &str, score: u64) -> Item {
+        Item {
+            id: 1,
+            title: Some(title.to_string()),
+            url: Some(url.to_string()),
+            score: Some(score),
+            descendants: None,
+            by: None,
+            time: None,
+            text: None,
+            kids: None,
+            parent: None,
+            parts: None,
+            kind: Some("story".to_string()),
+            dead: false,
+            deleted: false,
+        }
+    }
+
+    #[test]
+    fn matches_rules_with_all_conditions() {
+        let rules = vec![
+            WatchRule::default(),
+            WatchRule {
+                keyword: Some("rust".to_string()),
+                min_points: Some(100),
+                ..WatchRule::default()
+            },
+            WatchRule {
+                domain: Some("www.GitHub.com".to_string()),
+                ..WatchRule::default()
+            },
+        ];
+        let (watches, error) = WatchList::new(&rules);
+        assert_eq!(error, None);
+
+        assert_eq!(
+            watches.matching_rule(&story("Rust 2.0", "https://a.example", 150)),
+            Some("rust ≥100")
+        );
+        assert_eq!(
+            watches.matching_rule(&story("Rust 2.0", "https://a.example", 50)),
+            None
+        );
+        assert_eq!(
+            watches.matching_rule(&story("A tool", "https://gist.github.com/x", 1)),
+            Some("www.GitHub.com")
+        );
+
+        let mut dead = story("Rust", "https://github.com", 500);
+        dead.dead = true;
+        assert_eq!(watches.matching_rule(&dead), None);
+    }
+
+    #[test]
+    fn reports_invalid_rules() {
+        let rules = vec![WatchRule {
+            keyword: Some("/[/".to_string()),
+            ..WatchRule::default()
+        }];
+        let (watches, error) = WatchList::new(&rules);
+
+        assert!(watches.is_empty());
+        assert!(error.is_some_and(|error| error.starts_with("Invalid watch rule /[/")));
+    }
+}